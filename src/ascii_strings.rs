@@ -1,17 +1,25 @@
 //! Converter functions to convert trits/trytes to ASCII text.
 
-#[cfg(not(feature = "std"))]
-use alloc::string::String;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
 
 use crate::{
     constants::MAX_TRYTE_TRIPLET_ABS,
-    luts::TRYTE_CODE_TO_ASCII_CODE,
+    error::ConversionError,
+    error::ErrorKind,
     numbers::from_trytes_max11,
     types::Tryte,
-    util::unpad_right,
+    util::check_buf_len,
+    util::check_len_multiple_of,
+    util::check_tryte_str,
+    util::into_heapless_string,
 };
 
+#[cfg(feature = "alloc")]
+use crate::{luts::TRYTE_CODE_TO_ASCII_CODE, util::unpad_right};
+
 /// Converts trytes to an ASCII/UTF8 encoded string.
+#[cfg(feature = "alloc")]
 pub fn from_trytes(trytes: &[Tryte]) -> String {
     if trytes.is_empty() {
         return String::new();
@@ -19,55 +27,155 @@ pub fn from_trytes(trytes: &[Tryte]) -> String {
 
     #[cfg(not(feature = "no_checks"))]
     {
-        assert!(crate::util::is_trytes(trytes));
-        assert_eq!(0, trytes.len() % 3);
+        check_len_multiple_of(trytes.len(), 3).unwrap();
     }
 
-    inner(trytes)
+    try_inner(trytes).unwrap()
+}
+
+/// Tries to convert trytes to an ASCII/UTF8 encoded string.
+///
+/// Fails if a tryte triplet doesn't decode to two ASCII characters.
+#[cfg(feature = "alloc")]
+pub fn try_from_trytes(trytes: &[Tryte]) -> Result<String, ConversionError> {
+    if trytes.is_empty() {
+        return Ok(String::new());
+    }
+
+    check_len_multiple_of(trytes.len(), 3)?;
+
+    try_inner(trytes)
 }
 
 /// Converts a tryte string to an ASCII/UTF8 string.
+///
+/// The tryte string is checked even with `no_checks`, since every `Tryte` has to be one of `9A-Z`.
+#[cfg(feature = "alloc")]
 pub fn from_tryte_str(tryte_str: &str) -> String {
     if tryte_str.is_empty() {
         return String::new();
     };
 
-    #[cfg(not(feature = "no_checks"))]
-    {
-        assert!(crate::util::is_tryte_str(tryte_str));
-    }
+    check_tryte_str(tryte_str).unwrap();
+
+    try_inner(&padded_trytes(tryte_str)).unwrap()
+}
+
+/// Tries to convert a tryte string to an ASCII/UTF8 string.
+///
+/// Fails if a tryte triplet doesn't decode to two ASCII characters.
+#[cfg(feature = "alloc")]
+pub fn try_from_tryte_str(tryte_str: &str) -> Result<String, ConversionError> {
+    if tryte_str.is_empty() {
+        return Ok(String::new());
+    };
+
+    check_tryte_str(tryte_str)?;
+
+    try_inner(&padded_trytes(tryte_str))
+}
+
+/// Converts trytes to ASCII characters, writing them into `ascii`.
+///
+/// Returns the number of characters written.
+pub fn from_trytes_into(trytes: &[Tryte], ascii: &mut [u8]) -> Result<usize, ConversionError> {
+    check_len_multiple_of(trytes.len(), 3)?;
+    check_buf_len(ascii.len(), trytes.len() / 3 * 2)?;
+
+    write_ascii(trytes.chunks_exact(3).map(|t| [t[0], t[1], t[2]]), ascii)
+}
 
+/// Converts trytes to an ASCII `heapless::String`.
+///
+/// Fails if the string doesn't fit into its capacity `N`.
+pub fn from_trytes_heapless<const N: usize>(
+    trytes: &[Tryte],
+) -> Result<heapless::String<N>, ConversionError> {
+    into_heapless_string(|buf| from_trytes_into(trytes, buf))
+}
+
+/// Converts a tryte string to ASCII characters, writing them into `ascii`.
+///
+/// Returns the number of characters written.
+pub fn from_tryte_str_into(tryte_str: &str, ascii: &mut [u8]) -> Result<usize, ConversionError> {
+    check_tryte_str(tryte_str)?;
+
+    // Trailing 9s are padding and an incomplete last triplet is padded with 9s again, just like
+    // `from_tryte_str` does.
+    let trytes = tryte_str.trim_end_matches('9').as_bytes();
+    check_buf_len(ascii.len(), trytes.len().div_ceil(3) * 2)?;
+
+    let triplets = trytes.chunks(3).map(|t| {
+        let mut triplet = [Tryte::default(); 3];
+        triplet.iter_mut().zip(t.iter()).for_each(|(u, &c)| *u = Tryte(c));
+        triplet
+    });
+    write_ascii(triplets, ascii)
+}
+
+/// Converts a tryte string to an ASCII `heapless::String`.
+///
+/// Fails if the string doesn't fit into its capacity `N`.
+pub fn from_tryte_str_heapless<const N: usize>(
+    tryte_str: &str,
+) -> Result<heapless::String<N>, ConversionError> {
+    into_heapless_string(|buf| from_tryte_str_into(tryte_str, buf))
+}
+
+#[cfg(feature = "alloc")]
+fn padded_trytes(tryte_str: &str) -> Vec<Tryte> {
     // Remove 9s from the str
     // TODO: instead of removing first and then adding again we should be able to customize unpadding function
     let tryte_string = unpad_right(tryte_str);
 
-    let mut trytes = tryte_string.as_bytes().to_vec();
+    // The callers checked the tryte string.
+    let mut trytes: Vec<Tryte> = tryte_string.bytes().map(Tryte).collect();
 
     // make it a multiple of 3
     for _ in 0..trytes.len() % 3 {
         trytes.push(TRYTE_CODE_TO_ASCII_CODE[0]);
     }
 
-    inner(&trytes)
+    trytes
 }
 
 #[inline]
-fn inner(trytes: &[Tryte]) -> String {
+#[cfg(feature = "alloc")]
+fn try_inner(trytes: &[Tryte]) -> Result<String, ConversionError> {
     let mut ascii_chars = vec![0; trytes.len() / 3 * 2];
 
-    for i in 0..trytes.len() / 3 {
-        let index = from_trytes_max11(&trytes[(i * 3)..(i * 3 + 3)]) + MAX_TRYTE_TRIPLET_ABS;
+    let len = write_ascii(trytes.chunks_exact(3).map(|t| [t[0], t[1], t[2]]), &mut ascii_chars)?;
+    ascii_chars.truncate(len);
+
+    // Since all chars are ASCII 'unwrap' will never fail
+    Ok(String::from_utf8(ascii_chars).unwrap())
+}
 
-        ascii_chars[i * 2] = (index / 127) as u8;
-        ascii_chars[i * 2 + 1] = (index % 127) as u8;
+#[inline]
+fn write_ascii<I>(triplets: I, ascii: &mut [u8]) -> Result<usize, ConversionError>
+where
+    I: Iterator<Item = [Tryte; 3]>,
+{
+    let mut len = 0;
+
+    for (i, triplet) in triplets.enumerate() {
+        let index = from_trytes_max11(&triplet) + MAX_TRYTE_TRIPLET_ABS;
+
+        // Triplets that don't encode two ASCII characters can't be decoded.
+        if index / 127 > 127 {
+            return Err(ConversionError::new(ErrorKind::NonAscii, i * 3));
+        }
+
+        ascii[len] = (index / 127) as u8;
+        ascii[len + 1] = (index % 127) as u8;
+        len += 2;
     }
 
-    if ascii_chars[ascii_chars.len() - 1] == 0 {
-        ascii_chars.remove(ascii_chars.len() - 1);
+    if len > 0 && ascii[len - 1] == 0 {
+        len -= 1;
     }
 
-    // Since we checked all chars previously 'unwrap' should never fail
-    String::from_utf8(ascii_chars).unwrap()
+    Ok(len)
 }
 
 #[cfg(test)]
@@ -79,6 +187,45 @@ mod from_tryte_str_tests {
         assert_eq!("Hello", from_tryte_str("YEZNMEQWF"));
     }
 
+    #[test]
+    fn test_try_from_tryte_str() {
+        assert_eq!(Ok(String::from("Hello")), try_from_tryte_str("YEZNMEQWF"));
+
+        let err = try_from_tryte_str("YEZNMeQWF").unwrap_err();
+        assert_eq!(ErrorKind::InvalidTryte, err.kind());
+        assert_eq!(5, err.index());
+
+        let err = try_from_tryte_str("YEZMMM").unwrap_err();
+        assert_eq!(ErrorKind::NonAscii, err.kind());
+        assert_eq!(3, err.index());
+    }
+
+    #[test]
+    fn test_from_tryte_str_into() {
+        let mut ascii = [0_u8; 8];
+
+        assert_eq!(Ok(5), from_tryte_str_into("YEZNMEQWF999", &mut ascii));
+        assert_eq!(b"Hello", &ascii[..5]);
+
+        let err = from_tryte_str_into("YEZNMEQWFQWFQWF", &mut ascii).unwrap_err();
+        assert_eq!(ErrorKind::BufferTooSmall, err.kind());
+        assert_eq!(10, err.index());
+
+        // An incomplete last triplet is padded with 9s.
+        let m = crate::tryte_strings::from_ascii("M") + "999999";
+        for tryte_str in [m.as_str(), "YEZNM"].iter() {
+            let len = from_tryte_str_into(tryte_str, &mut ascii).unwrap();
+            assert_eq!(from_tryte_str(tryte_str).as_bytes(), &ascii[..len]);
+        }
+        assert_eq!(Ok(1), from_tryte_str_into(&m, &mut ascii));
+    }
+
+    #[test]
+    fn test_from_tryte_str_heapless() {
+        assert_eq!("Hello", from_tryte_str_heapless::<8>("YEZNMEQWF").unwrap().as_str());
+        assert!(from_tryte_str_heapless::<8>("YEZNMEQWFQWFQWF").is_err());
+    }
+
     #[test]
     fn test_from_tryte_str_inverse() {
         assert_eq!(
@@ -0,0 +1,568 @@
+//! An arbitrary precision integer that converts between balanced ternary and binary.
+//!
+//! `BigTernary` interprets trits and trytes of any length as a number, e.g. 243 trit hashes, and
+//! converts it to and from big-endian two's complement bytes. It is implemented without any
+//! dependencies and only needs the `alloc` feature.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use crate::{
+    error::ConversionError,
+    error::ErrorKind,
+    trits,
+    trytes,
+    types::Sign,
+    types::Trit,
+    types::Tryte,
+    types::S129,
+};
+
+/// The number of bits in a limb of the magnitude.
+const LIMB_BITS: usize = 32;
+
+/// An arbitrary precision signed integer.
+///
+/// The number is stored as a sign and a binary magnitude. Zero always has a positive sign, so two
+/// equal numbers have the same representation.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BigTernary {
+    negative: bool,
+    /// Limbs of the magnitude, least significant first, without leading zero limbs.
+    magnitude: Vec<u32>,
+}
+
+impl BigTernary {
+    /// Returns the number `0`.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    /// Returns whether the number is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Interprets balanced trits, least significant trit first, as a number.
+    pub fn from_trits(trits: &[Trit]) -> Self {
+        // The positive and the negative trits are accumulated separately, so the magnitudes never
+        // have to change their sign.
+        let mut pos = Vec::new();
+        let mut neg = Vec::new();
+
+        for &trit in trits.iter().rev() {
+            mul_add_small(&mut pos, 3, u32::from(trit == Trit::Pos));
+            mul_add_small(&mut neg, 3, u32::from(trit == Trit::Neg));
+        }
+
+        Self::from_magnitude(false, pos) - Self::from_magnitude(false, neg)
+    }
+
+    /// Interprets trytes, least significant tryte first, as a number.
+    pub fn from_trytes(trytes: &[Tryte]) -> Self {
+        Self::from_trits(&trits::from_trytes(trytes))
+    }
+
+    /// Converts the number to the smallest number of trits that can represent it, least
+    /// significant trit first.
+    pub fn to_trits(&self) -> Vec<Trit> {
+        let (pos, neg) =
+            if self.negative { (Trit::Neg, Trit::Pos) } else { (Trit::Pos, Trit::Neg) };
+
+        let mut magnitude = self.magnitude.clone();
+        let mut trits = Vec::new();
+
+        while !magnitude.is_empty() {
+            let trit = match div_small(&mut magnitude, 3) {
+                1 => pos,
+                2 => {
+                    // 2 = 3 - 1, so the next trit has to be incremented.
+                    mul_add_small(&mut magnitude, 1, 1);
+                    neg
+                }
+                _ => Trit::Zero,
+            };
+            trits.push(trit);
+        }
+
+        trits
+    }
+
+    /// Converts the number to `len` trits, least significant trit first.
+    ///
+    /// Fails if the number can't be represented by that many trits.
+    pub fn to_trits_len(&self, len: usize) -> Result<Vec<Trit>, ConversionError> {
+        let mut trits = self.to_trits();
+        if trits.len() > len {
+            return Err(ConversionError::new(ErrorKind::OutOfRange, 0));
+        }
+
+        trits.resize(len, Trit::Zero);
+        Ok(trits)
+    }
+
+    /// Converts the number to the smallest number of trytes that can represent it, least
+    /// significant tryte first.
+    pub fn to_trytes(&self) -> Vec<Tryte> {
+        let mut trits = self.to_trits();
+        trits.resize(trits.len().div_ceil(3) * 3, Trit::Zero);
+        trytes::from_trits(&trits)
+    }
+
+    /// Converts the number to `len` trytes, least significant tryte first.
+    ///
+    /// Fails if the number can't be represented by that many trytes.
+    pub fn to_trytes_len(&self, len: usize) -> Result<Vec<Tryte>, ConversionError> {
+        Ok(trytes::from_trits(&self.to_trits_len(len * 3)?))
+    }
+
+    /// Interprets big-endian two's complement bytes as a number.
+    ///
+    /// An empty slice is zero.
+    pub fn from_be_bytes(bytes: &[u8]) -> Self {
+        let negative = bytes.first().is_some_and(|&b| b & 0x80 != 0);
+
+        let mut magnitude: Vec<u32> = bytes
+            .rchunks(4)
+            .map(|chunk| chunk.iter().fold(0, |limb, &b| (limb << 8) | u32::from(b)))
+            .collect();
+
+        if negative {
+            // Sign extend the most significant limb before negating the two's complement.
+            let top_bits = bytes.len() % 4 * 8;
+            if top_bits != 0 {
+                *magnitude.last_mut().unwrap() |= u32::MAX << top_bits;
+            }
+            negate_twos_complement(&mut magnitude);
+        }
+
+        Self::from_magnitude(negative, magnitude)
+    }
+
+    /// Converts the number to the smallest number of big-endian two's complement bytes that can
+    /// represent it.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut len = bit_len(&self.magnitude).div_ceil(8).max(1);
+        if !self.fits_bytes(len) {
+            len += 1;
+        }
+
+        self.write_be_bytes(len)
+    }
+
+    /// Converts the number to `len` big-endian two's complement bytes.
+    ///
+    /// Fails if the number can't be represented by that many bytes.
+    pub fn to_be_bytes_len(&self, len: usize) -> Result<Vec<u8>, ConversionError> {
+        if !self.fits_bytes(len) {
+            return Err(ConversionError::new(ErrorKind::OutOfRange, 0));
+        }
+
+        Ok(self.write_be_bytes(len))
+    }
+
+    /// Returns whether the number is in the range of `len` two's complement bytes,
+    /// `-2^(8*len-1)..2^(8*len-1)`.
+    fn fits_bytes(&self, len: usize) -> bool {
+        let bits = bit_len(&self.magnitude);
+
+        // -2^(8*len-1) is the only number whose magnitude needs all bits.
+        let is_min = self.negative
+            && bits == len * 8
+            && self.magnitude.iter().map(|l| l.count_ones()).sum::<u32>() == 1;
+
+        self.is_zero() || bits < len * 8 || is_min
+    }
+
+    fn write_be_bytes(&self, len: usize) -> Vec<u8> {
+        let mut limbs = self.magnitude.clone();
+        limbs.resize((len * 8).div_ceil(LIMB_BITS).max(limbs.len()), 0);
+
+        if self.negative {
+            negate_twos_complement(&mut limbs);
+        }
+
+        (0..len).rev().map(|i| (limbs[i / 4] >> (i % 4 * 8)) as u8).collect()
+    }
+
+    /// Creates a number from a sign and a not necessarily normalized magnitude.
+    fn from_magnitude(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+
+        let negative = negative && !magnitude.is_empty();
+        Self { negative, magnitude }
+    }
+
+    fn add_signed(&self, other: &Self, other_negative: bool) -> Self {
+        if self.negative == other_negative {
+            return Self::from_magnitude(
+                self.negative,
+                add_magnitudes(&self.magnitude, &other.magnitude),
+            );
+        }
+
+        match cmp_magnitudes(&self.magnitude, &other.magnitude) {
+            Ordering::Less => Self::from_magnitude(
+                other_negative,
+                sub_magnitudes(&other.magnitude, &self.magnitude),
+            ),
+            _ => Self::from_magnitude(
+                self.negative,
+                sub_magnitudes(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+}
+
+impl Ord for BigTernary {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => cmp_magnitudes(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitudes(&other.magnitude, &self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for BigTernary {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigTernary {
+    type Output = BigTernary;
+
+    fn neg(mut self) -> BigTernary {
+        self.negative = !self.negative && !self.is_zero();
+        self
+    }
+}
+
+impl Neg for &BigTernary {
+    type Output = BigTernary;
+
+    fn neg(self) -> BigTernary {
+        -self.clone()
+    }
+}
+
+impl Add for &BigTernary {
+    type Output = BigTernary;
+
+    fn add(self, other: &BigTernary) -> BigTernary {
+        self.add_signed(other, other.negative)
+    }
+}
+
+impl Sub for &BigTernary {
+    type Output = BigTernary;
+
+    fn sub(self, other: &BigTernary) -> BigTernary {
+        self.add_signed(other, !other.negative)
+    }
+}
+
+impl Mul for &BigTernary {
+    type Output = BigTernary;
+
+    fn mul(self, other: &BigTernary) -> BigTernary {
+        BigTernary::from_magnitude(
+            self.negative != other.negative,
+            mul_magnitudes(&self.magnitude, &other.magnitude),
+        )
+    }
+}
+
+macro_rules! forward_binop {
+    ($imp:ident, $method:ident) => {
+        impl $imp for BigTernary {
+            type Output = BigTernary;
+
+            fn $method(self, other: BigTernary) -> BigTernary {
+                (&self).$method(&other)
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add);
+forward_binop!(Sub, sub);
+forward_binop!(Mul, mul);
+
+impl From<i64> for BigTernary {
+    fn from(value: i64) -> Self {
+        Self::from(i128::from(value))
+    }
+}
+
+impl From<i128> for BigTernary {
+    fn from(value: i128) -> Self {
+        Self::from(S129::from(value))
+    }
+}
+
+impl From<S129> for BigTernary {
+    fn from(value: S129) -> Self {
+        let magnitude = (0..128 / LIMB_BITS).map(|i| (value.1 >> (i * LIMB_BITS)) as u32).collect();
+        Self::from_magnitude(value.is_negative(), magnitude)
+    }
+}
+
+impl TryFrom<&BigTernary> for S129 {
+    type Error = ConversionError;
+
+    fn try_from(value: &BigTernary) -> Result<Self, Self::Error> {
+        if bit_len(&value.magnitude) > 128 {
+            return Err(ConversionError::new(ErrorKind::OutOfRange, 0));
+        }
+
+        let magnitude = value
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0_u128, |m, &limb| (m << LIMB_BITS) | u128::from(limb));
+        let sign = if value.negative { Sign::Neg } else { Sign::Pos };
+
+        Ok(S129::new(sign, magnitude))
+    }
+}
+
+impl fmt::Display for BigTernary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+
+        // Split the magnitude into decimal chunks of 9 digits, least significant first.
+        let mut magnitude = self.magnitude.clone();
+        let mut chunks = Vec::new();
+        while !magnitude.is_empty() {
+            chunks.push(div_small(&mut magnitude, CHUNK));
+        }
+
+        if self.negative {
+            f.write_str("-")?;
+        }
+
+        match chunks.split_last() {
+            None => f.write_str("0"),
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                rest.iter().rev().try_for_each(|chunk| write!(f, "{:09}", chunk))
+            }
+        }
+    }
+}
+
+/// Returns the number of significant bits of a normalized magnitude.
+fn bit_len(magnitude: &[u32]) -> usize {
+    match magnitude.last() {
+        Some(&top) => magnitude.len() * LIMB_BITS - top.leading_zeros() as usize,
+        None => 0,
+    }
+}
+
+fn cmp_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+
+    let mut sum = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0_u64;
+
+    for (i, &limb) in long.iter().enumerate() {
+        let s = u64::from(limb) + u64::from(short.get(i).copied().unwrap_or(0)) + carry;
+        sum.push(s as u32);
+        carry = s >> LIMB_BITS;
+    }
+    sum.push(carry as u32);
+
+    sum
+}
+
+/// Subtracts `b` from `a`, which must not be smaller than `b`.
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = false;
+
+    for (i, &limb) in a.iter().enumerate() {
+        let (d, b1) = limb.overflowing_sub(b.get(i).copied().unwrap_or(0));
+        let (d, b2) = d.overflowing_sub(u32::from(borrow));
+        difference.push(d);
+        borrow = b1 || b2;
+    }
+
+    difference
+}
+
+fn mul_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0_u32; a.len() + b.len()];
+
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0_u64;
+        for (j, &y) in b.iter().enumerate() {
+            let p = u64::from(x) * u64::from(y) + u64::from(product[i + j]) + carry;
+            product[i + j] = p as u32;
+            carry = p >> LIMB_BITS;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+
+    product
+}
+
+/// Computes `magnitude * factor + summand` in place.
+fn mul_add_small(magnitude: &mut Vec<u32>, factor: u32, summand: u32) {
+    let mut carry = u64::from(summand);
+
+    for limb in magnitude.iter_mut() {
+        let p = u64::from(*limb) * u64::from(factor) + carry;
+        *limb = p as u32;
+        carry = p >> LIMB_BITS;
+    }
+
+    if carry != 0 {
+        magnitude.push(carry as u32);
+    }
+}
+
+/// Divides the magnitude in place and returns the remainder.
+fn div_small(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0_u64;
+
+    for limb in magnitude.iter_mut().rev() {
+        let dividend = (remainder << LIMB_BITS) | u64::from(*limb);
+        *limb = (dividend / u64::from(divisor)) as u32;
+        remainder = dividend % u64::from(divisor);
+    }
+
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+
+    remainder as u32
+}
+
+/// Replaces the limbs by their two's complement, i.e. inverts them and adds one.
+fn negate_twos_complement(limbs: &mut [u32]) {
+    let mut carry = true;
+
+    for limb in limbs.iter_mut() {
+        let (l, c) = (!*limb).overflowing_add(u32::from(carry));
+        *limb = l;
+        carry = c;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn big(value: i128) -> BigTernary {
+        BigTernary::from(value)
+    }
+
+    #[test]
+    fn test_trits() {
+        assert_eq!(big(0), BigTernary::from_trits(&[]));
+        assert_eq!(big(-2), BigTernary::from_trits(&trits::from_raw(&[1, -1, 0])));
+        assert_eq!(big(-13), BigTernary::from_trytes(&trytes::from_tryte_str("N")));
+        assert!(big(0).to_trits().is_empty());
+        assert_eq!(trits::from_raw(&[1, -1]), big(-2).to_trits());
+        assert_eq!(trits::from_raw(&[1, -1, 0, 0]), big(-2).to_trits_len(4).unwrap());
+        assert_eq!(ErrorKind::OutOfRange, big(14).to_trits_len(2).unwrap_err().kind());
+        assert_eq!(trytes::from_tryte_str("NA"), big(14).to_trytes());
+        assert_eq!(trytes::from_tryte_str("Z99"), big(-1).to_trytes_len(3).unwrap());
+
+        // A 243 trit number of only positive trits is (3^243-1)/2.
+        let max = BigTernary::from_trits(&[Trit::Pos; 243]);
+        assert_eq!(vec![Trit::Pos; 243], max.to_trits());
+        assert_eq!(vec![Trit::Neg; 243], (-max).to_trits());
+    }
+
+    #[test]
+    fn test_trits_roundtrip() {
+        use rand::{thread_rng, Rng};
+
+        let mut rng = thread_rng();
+
+        for _ in 0..100 {
+            let len = rng.gen_range(1, 300);
+            let mut trits: Vec<Trit> =
+                (0..len).map(|_| Trit::try_from(rng.gen_range(-1_i8, 2)).unwrap()).collect();
+            trits.push(Trit::Pos);
+
+            assert_eq!(trits, BigTernary::from_trits(&trits).to_trits());
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let values = [0, 1, -1, 7, -13, i64::MAX as i128, i64::MIN as i128, 1 << 100, -(1 << 90)];
+
+        for &a in values.iter() {
+            for &b in values.iter() {
+                assert_eq!(big(a + b), big(a) + big(b));
+                assert_eq!(big(a - b), big(a) - big(b));
+                assert_eq!(a.cmp(&b), big(a).cmp(&big(b)));
+                if let Some(p) = a.checked_mul(b) {
+                    assert_eq!(big(p), big(a) * big(b));
+                }
+            }
+        }
+
+        let large = big(i128::MAX) * big(i128::MIN);
+        assert_eq!(
+            "-28948022309329048855892746252171976963147354982949671778132708698262398304256",
+            large.to_string()
+        );
+        assert_eq!(big(0), &large - &large);
+        assert_eq!(big(i128::MIN), large - big(i128::MAX) * big(i128::MIN) + big(i128::MIN));
+        assert_eq!(big(0), -big(0));
+    }
+
+    #[test]
+    fn test_be_bytes() {
+        assert_eq!(big(0), BigTernary::from_be_bytes(&[]));
+        assert_eq!(big(-1), BigTernary::from_be_bytes(&[0xff, 0xff, 0xff, 0xff, 0xff]));
+        assert_eq!(big(-129), BigTernary::from_be_bytes(&[0xff, 0x7f]));
+        assert_eq!(big(128), BigTernary::from_be_bytes(&[0x00, 0x80]));
+
+        assert_eq!(vec![0], big(0).to_be_bytes());
+        assert_eq!(vec![0x80], big(-128).to_be_bytes());
+        assert_eq!(vec![0x00, 0x80], big(128).to_be_bytes());
+        assert_eq!(vec![0xff, 0x7f], big(-129).to_be_bytes());
+        assert_eq!(vec![0xff, 0xff, 0xff, 0xff, 0xff], big(-1).to_be_bytes_len(5).unwrap());
+        assert_eq!(ErrorKind::OutOfRange, big(128).to_be_bytes_len(1).unwrap_err().kind());
+
+        for &value in [i128::MAX, i128::MIN, -(1 << 64), 1 << 31, -12345678901234567].iter() {
+            assert_eq!(value.to_be_bytes().to_vec(), big(value).to_be_bytes_len(16).unwrap());
+            assert_eq!(big(value), BigTernary::from_be_bytes(&value.to_be_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_s129() {
+        assert_eq!(Ok(S129::MIN), S129::try_from(&BigTernary::from(S129::MIN)));
+        assert!(S129::try_from(&(BigTernary::from(S129::MAX) + big(1))).is_err());
+    }
+}
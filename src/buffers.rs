@@ -0,0 +1,421 @@
+//! Owned and borrowed containers for trits and trytes.
+//!
+//! `TritBuf` and `TryteBuf` own their data like `String`, while `Trits` and `TryteStr` borrow it
+//! like `&str`. Since trits and trytes are validated on construction, the containers can be passed
+//! around and converted without checking their content again.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use core::{fmt, iter::FromIterator, ops::Deref, ops::DerefMut, slice, str::FromStr};
+
+use crate::{
+    ascii_strings,
+    bytes,
+    error::ConversionError,
+    error::ErrorKind,
+    trits,
+    trytes,
+    types::Byte,
+    types::Trit,
+    types::Tryte,
+};
+
+/// An owned sequence of trits.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TritBuf(Vec<Trit>);
+
+/// A borrowed sequence of trits.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Trits<'a>(&'a [Trit]);
+
+/// An owned sequence of trytes.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TryteBuf(Vec<Tryte>);
+
+/// A borrowed sequence of trytes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TryteStr<'a>(&'a [Tryte]);
+
+impl TritBuf {
+    /// Creates an empty `TritBuf`.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Creates an empty `TritBuf` with space for at least `capacity` trits.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Appends a trit.
+    pub fn push(&mut self, trit: Trit) {
+        self.0.push(trit);
+    }
+
+    /// Borrows the whole buffer as `Trits`.
+    pub fn as_trits(&self) -> Trits<'_> {
+        Trits(&self.0)
+    }
+
+    /// Returns the underlying vector.
+    pub fn into_inner(self) -> Vec<Trit> {
+        self.0
+    }
+
+    /// Converts the trits to trytes. Fails if the length is not a multiple of 3.
+    pub fn to_trytes(&self) -> Result<TryteBuf, ConversionError> {
+        self.as_trits().to_trytes()
+    }
+
+    /// Converts the trits to bytes. Fails if the length is not a multiple of 9.
+    pub fn to_bytes(&self) -> Result<Vec<Byte>, ConversionError> {
+        self.as_trits().to_bytes()
+    }
+
+    /// Decodes the trits to an ASCII string. Fails if the length is not a multiple of 9 or the
+    /// trits don't encode ASCII text.
+    pub fn to_ascii(&self) -> Result<String, ConversionError> {
+        self.as_trits().to_ascii()
+    }
+}
+
+impl<'a> Trits<'a> {
+    /// Borrows a slice of trits.
+    pub fn new(trits: &'a [Trit]) -> Self {
+        Self(trits)
+    }
+
+    /// Returns the underlying slice with its original lifetime.
+    pub fn as_slice(&self) -> &'a [Trit] {
+        self.0
+    }
+
+    /// Copies the trits into a new `TritBuf`.
+    pub fn to_trit_buf(&self) -> TritBuf {
+        TritBuf(self.0.to_vec())
+    }
+
+    /// Converts the trits to trytes. Fails if the length is not a multiple of 3.
+    pub fn to_trytes(&self) -> Result<TryteBuf, ConversionError> {
+        trytes::try_from_trits(self.0).map(TryteBuf)
+    }
+
+    /// Converts the trits to bytes. Fails if the length is not a multiple of 9.
+    pub fn to_bytes(&self) -> Result<Vec<Byte>, ConversionError> {
+        bytes::try_from_trits(self.0)
+    }
+
+    /// Decodes the trits to an ASCII string. Fails if the length is not a multiple of 9 or the
+    /// trits don't encode ASCII text.
+    pub fn to_ascii(&self) -> Result<String, ConversionError> {
+        self.to_trytes()?.to_ascii()
+    }
+}
+
+impl TryteBuf {
+    /// Creates an empty `TryteBuf`.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Creates an empty `TryteBuf` with space for at least `capacity` trytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Appends a tryte.
+    pub fn push(&mut self, tryte: Tryte) {
+        self.0.push(tryte);
+    }
+
+    /// Borrows the whole buffer as `TryteStr`.
+    pub fn as_tryte_str(&self) -> TryteStr<'_> {
+        TryteStr(&self.0)
+    }
+
+    /// Returns the underlying vector.
+    pub fn into_inner(self) -> Vec<Tryte> {
+        self.0
+    }
+
+    /// Converts the trytes to trits.
+    pub fn to_trits(&self) -> TritBuf {
+        self.as_tryte_str().to_trits()
+    }
+
+    /// Converts the trytes to bytes. Fails if the length is not a multiple of 3.
+    pub fn to_bytes(&self) -> Result<Vec<Byte>, ConversionError> {
+        self.as_tryte_str().to_bytes()
+    }
+
+    /// Decodes the trytes to an ASCII string. Fails if the length is not a multiple of 3 or the
+    /// trytes don't encode ASCII text.
+    pub fn to_ascii(&self) -> Result<String, ConversionError> {
+        self.as_tryte_str().to_ascii()
+    }
+}
+
+impl<'a> TryteStr<'a> {
+    /// Borrows a slice of trytes.
+    pub fn new(trytes: &'a [Tryte]) -> Self {
+        Self(trytes)
+    }
+
+    /// Returns the underlying slice with its original lifetime.
+    pub fn as_slice(&self) -> &'a [Tryte] {
+        self.0
+    }
+
+    /// Copies the trytes into a new `TryteBuf`.
+    pub fn to_tryte_buf(&self) -> TryteBuf {
+        TryteBuf(self.0.to_vec())
+    }
+
+    /// Converts the trytes to trits.
+    pub fn to_trits(&self) -> TritBuf {
+        TritBuf(trits::from_trytes(self.0))
+    }
+
+    /// Converts the trytes to bytes. Fails if the length is not a multiple of 3.
+    pub fn to_bytes(&self) -> Result<Vec<Byte>, ConversionError> {
+        bytes::try_from_trytes(self.0)
+    }
+
+    /// Decodes the trytes to an ASCII string. Fails if the length is not a multiple of 3 or the
+    /// trytes don't encode ASCII text.
+    pub fn to_ascii(&self) -> Result<String, ConversionError> {
+        ascii_strings::try_from_trytes(self.0)
+    }
+}
+
+macro_rules! impl_slice_traits {
+    ($owned:ident, $borrowed:ident, $item:ty) => {
+        impl Deref for $owned {
+            type Target = [$item];
+
+            fn deref(&self) -> &[$item] {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $owned {
+            fn deref_mut(&mut self) -> &mut [$item] {
+                &mut self.0
+            }
+        }
+
+        impl AsRef<[$item]> for $owned {
+            fn as_ref(&self) -> &[$item] {
+                &self.0
+            }
+        }
+
+        impl From<Vec<$item>> for $owned {
+            fn from(items: Vec<$item>) -> Self {
+                Self(items)
+            }
+        }
+
+        impl<'a> From<$borrowed<'a>> for $owned {
+            fn from(items: $borrowed<'a>) -> Self {
+                Self(items.0.to_vec())
+            }
+        }
+
+        impl FromIterator<$item> for $owned {
+            fn from_iter<I: IntoIterator<Item = $item>>(iter: I) -> Self {
+                Self(iter.into_iter().collect())
+            }
+        }
+
+        impl Extend<$item> for $owned {
+            fn extend<I: IntoIterator<Item = $item>>(&mut self, iter: I) {
+                self.0.extend(iter)
+            }
+        }
+
+        impl IntoIterator for $owned {
+            type Item = $item;
+            type IntoIter = <Vec<$item> as IntoIterator>::IntoIter;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.into_iter()
+            }
+        }
+
+        impl<'a> IntoIterator for &'a $owned {
+            type Item = &'a $item;
+            type IntoIter = slice::Iter<'a, $item>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter()
+            }
+        }
+
+        impl<'a> Deref for $borrowed<'a> {
+            type Target = [$item];
+
+            fn deref(&self) -> &[$item] {
+                self.0
+            }
+        }
+
+        impl<'a> AsRef<[$item]> for $borrowed<'a> {
+            fn as_ref(&self) -> &[$item] {
+                self.0
+            }
+        }
+
+        impl<'a> From<&'a [$item]> for $borrowed<'a> {
+            fn from(items: &'a [$item]) -> Self {
+                Self(items)
+            }
+        }
+
+        impl<'a> From<&'a $owned> for $borrowed<'a> {
+            fn from(items: &'a $owned) -> Self {
+                Self(&items.0)
+            }
+        }
+
+        impl<'a> IntoIterator for $borrowed<'a> {
+            type Item = &'a $item;
+            type IntoIter = slice::Iter<'a, $item>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter()
+            }
+        }
+    };
+}
+
+impl_slice_traits!(TritBuf, Trits, Trit);
+impl_slice_traits!(TryteBuf, TryteStr, Tryte);
+
+/// Trits are displayed as `-`, `0` and `+`.
+impl<'a> fmt::Display for Trits<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for trit in self.0 {
+            let c = match trit {
+                Trit::Neg => '-',
+                Trit::Zero => '0',
+                Trit::Pos => '+',
+            };
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TritBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_trits().fmt(f)
+    }
+}
+
+/// Trytes are displayed as a tryte string.
+impl<'a> fmt::Display for TryteStr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for tryte in self.0 {
+            write!(f, "{}", tryte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TryteBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_tryte_str().fmt(f)
+    }
+}
+
+/// Parses a string of `-`, `0` and `+` characters.
+impl FromStr for TritBuf {
+    type Err = ConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.bytes()
+            .enumerate()
+            .map(|(i, c)| match c {
+                b'-' => Ok(Trit::Neg),
+                b'0' => Ok(Trit::Zero),
+                b'+' => Ok(Trit::Pos),
+                _ => Err(ConversionError::new(ErrorKind::InvalidTrit, i)),
+            })
+            .collect()
+    }
+}
+
+/// Parses a tryte string.
+impl FromStr for TryteBuf {
+    type Err = ConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        trytes::try_from_tryte_str(s).map(TryteBuf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_tryte_buf_display_from_str() {
+        let trytes: TryteBuf = "HELLO9".parse().unwrap();
+        assert_eq!(6, trytes.len());
+        assert_eq!("HELLO9", trytes.to_string());
+        assert_eq!("LL", TryteStr::from(&trytes[2..4]).to_string());
+
+        let err = "HELLo9".parse::<TryteBuf>().unwrap_err();
+        assert_eq!(ErrorKind::InvalidTryte, err.kind());
+        assert_eq!(4, err.index());
+    }
+
+    #[test]
+    fn test_trit_buf_display_from_str() {
+        let trits: TritBuf = "-0+".parse().unwrap();
+        assert_eq!(&[Trit::Neg, Trit::Zero, Trit::Pos], &trits[..]);
+        assert_eq!("-0+", trits.to_string());
+        assert_eq!("0+", Trits::from(&trits[1..]).to_string());
+
+        let err = "-01".parse::<TritBuf>().unwrap_err();
+        assert_eq!(ErrorKind::InvalidTrit, err.kind());
+        assert_eq!(2, err.index());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let trytes: TryteBuf = "YEZNMEQWF".parse().unwrap();
+        let trits = trytes.to_trits();
+
+        assert_eq!(27, trits.len());
+        assert_eq!(trytes, trits.to_trytes().unwrap());
+        assert_eq!(trytes.to_bytes().unwrap(), trits.to_bytes().unwrap());
+        assert_eq!("Hello", trytes.to_ascii().unwrap());
+        assert_eq!("Hello", trits.as_trits().to_ascii().unwrap());
+
+        assert_eq!(
+            ErrorKind::InvalidLength,
+            Trits::from(&trits[..4]).to_trytes().unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn test_iteration() {
+        let trits: TritBuf = "+0-".parse().unwrap();
+        let inverted: TritBuf = trits
+            .iter()
+            .map(|t| match t {
+                Trit::Neg => Trit::Pos,
+                Trit::Zero => Trit::Zero,
+                Trit::Pos => Trit::Neg,
+            })
+            .collect();
+        assert_eq!("-0+", inverted.to_string());
+        assert_eq!(3, trits.as_trits().into_iter().count());
+    }
+}
@@ -1,67 +1,163 @@
 //! Converter functions that convert various datatypes to Bytes.
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::vec::Vec;
 
 use crate::{
     constants::SIG_MSG_FRG_SIZE_BYTES,
     constants::TRANSACTION_SIZE_BYTES,
+    encoding::TritEncoding,
+    error::ConversionError,
+    error::ErrorKind,
     luts::TRYTE_CODE_TO_ASCII_CODE,
+    simd,
+    trits::write_from_bytes,
     types::Byte,
     types::Trit,
     types::Tryte,
+    util::as_array,
+    util::assert_len_ratio,
+    util::check_buf_len,
+    util::check_bytes,
+    util::check_len,
+    util::check_len_multiple_of,
+    util::into_heapless,
 };
 
-const TRYTE_9: u8 = TRYTE_CODE_TO_ASCII_CODE[0];
-const TRYTE_A: u8 = TRYTE_CODE_TO_ASCII_CODE[1];
+const TRYTE_9: u8 = TRYTE_CODE_TO_ASCII_CODE[0].0;
+const TRYTE_A: u8 = TRYTE_CODE_TO_ASCII_CODE[1].0;
 
-macro_rules! from_trytes_conv {
-    ($func_name:ident, $length:expr) => {
-        /// Converts fixed-sized slices of trytes to bytes.
-        pub fn $func_name(trytes: &[Tryte]) -> [Byte; $length] {
-            #[cfg(not(feature = "no_checks"))]
-            {
-                assert_eq!(0, trytes.len() % 3);
-                assert_eq!($length, trytes.len() / 3 * 2);
-            }
-            let mut bytes = [0_u8; $length];
+/// The 9/2 encoding of this module, for code that is generic over `TritEncoding`s.
+///
+/// Unlike the converter functions of this module, it pads the last pair of bytes with zero trits
+/// if the number of trits is not a multiple of 9. Decoding fails if the padding trits aren't zero.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Bytes92;
+
+impl TritEncoding for Bytes92 {
+    fn packed_len(num_trits: usize) -> usize {
+        num_trits.div_ceil(9) * 2
+    }
 
-            for i in 0..trytes.len() / 3 {
-                let t0 = trytes[3 * i];
-                let t1 = trytes[3 * i + 1];
-                let t2 = trytes[3 * i + 2];
+    fn encode_into(trits: &[Trit], bytes: &mut [u8]) -> Result<usize, ConversionError> {
+        let len = Self::packed_len(trits.len());
+        check_buf_len(bytes.len(), len)?;
 
-                let i0 = if t0 == TRYTE_9 { 0 } else { t0 - TRYTE_A + 1 };
-                let i1 = if t1 == TRYTE_9 { 0 } else { t1 - TRYTE_A + 1 };
-                let i2 = if t2 == TRYTE_9 { 0 } else { t2 - TRYTE_A + 1 };
+        let full = trits.len() / 9 * 9;
+        write_from_trits(&trits[..full], &mut bytes[..full / 9 * 2]);
+        if full < trits.len() {
+            let mut last = [Trit::Zero; 9];
+            last[..trits.len() - full].copy_from_slice(&trits[full..]);
+            write_from_trits(&last, &mut bytes[full / 9 * 2..len]);
+        }
+        Ok(len)
+    }
 
-                bytes[2 * i] = i0 * 8 + i2 % 8;
-                bytes[2 * i + 1] = i1 * 8 + i2 / 8;
+    fn decode_into(
+        bytes: &[u8],
+        num_trits: usize,
+        trits: &mut [Trit],
+    ) -> Result<usize, ConversionError> {
+        check_len(bytes.len(), Self::packed_len(num_trits))?;
+        check_bytes(bytes)?;
+        check_buf_len(trits.len(), num_trits)?;
+
+        let full = num_trits / 9 * 9;
+        write_from_bytes(&bytes[..full / 9 * 2], &mut trits[..full]);
+        if full < num_trits {
+            let mut last = [Trit::Zero; 9];
+            write_from_bytes(&bytes[full / 9 * 2..], &mut last);
+            if last[num_trits - full..].iter().any(|&t| t != Trit::Zero) {
+                return Err(ConversionError::new(ErrorKind::OutOfRange, full / 9 * 2));
             }
-            bytes
+            trits[full..num_trits].copy_from_slice(&last[..num_trits - full]);
+        }
+        Ok(num_trits)
+    }
+}
+
+/// Converts an array of trytes to an array of bytes.
+///
+/// `M` has to be `N / 3 * 2`, which is checked at compile time.
+pub fn from_trytes_array<const N: usize, const M: usize>(trytes: &[Tryte; N]) -> [Byte; M] {
+    assert_len_ratio::<N, M, 2, 3>();
+
+    let mut bytes = [0_u8; M];
+    write_from_trytes(trytes, &mut bytes);
+    bytes
+}
+
+macro_rules! from_trytes_conv {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
+        /// Converts fixed-sized slices of trytes to bytes.
+        pub fn $func_name(trytes: &[Tryte]) -> [Byte; $length] {
+            from_trytes_array::<{ $length / 2 * 3 }, $length>(as_array(trytes).unwrap())
+        }
+
+        /// Tries to convert fixed-sized slices of trytes to bytes.
+        pub fn $try_func_name(trytes: &[Tryte]) -> Result<[Byte; $length], ConversionError> {
+            Ok(from_trytes_array::<{ $length / 2 * 3 }, $length>(as_array(trytes)?))
         }
     };
 }
 
-from_trytes_conv!(from_trytes_all, TRANSACTION_SIZE_BYTES);
-from_trytes_conv!(from_trytes_sig, SIG_MSG_FRG_SIZE_BYTES);
-from_trytes_conv!(from_trytes_81, 54);
-from_trytes_conv!(from_trytes_27, 18);
-from_trytes_conv!(from_trytes_9, 6);
+from_trytes_conv!(from_trytes_all, try_from_trytes_all, TRANSACTION_SIZE_BYTES);
+from_trytes_conv!(from_trytes_sig, try_from_trytes_sig, SIG_MSG_FRG_SIZE_BYTES);
+from_trytes_conv!(from_trytes_81, try_from_trytes_81, 54);
+from_trytes_conv!(from_trytes_27, try_from_trytes_27, 18);
+from_trytes_conv!(from_trytes_9, try_from_trytes_9, 6);
 
 /// Converts arbitrary slices of trytes to bytes.
+#[cfg(feature = "alloc")]
 pub fn from_trytes(trytes: &[Tryte]) -> Vec<Byte> {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert_eq!(0, trytes.len() % 3);
+        check_len_multiple_of(trytes.len(), 3).unwrap();
     }
 
     let mut bytes = vec![0_u8; trytes.len() / 3 * 2];
+    write_from_trytes(trytes, &mut bytes);
+    bytes
+}
+
+/// Tries to convert arbitrary slices of trytes to bytes.
+#[cfg(feature = "alloc")]
+pub fn try_from_trytes(trytes: &[Tryte]) -> Result<Vec<Byte>, ConversionError> {
+    check_len_multiple_of(trytes.len(), 3)?;
+
+    let mut bytes = vec![0_u8; trytes.len() / 3 * 2];
+    write_from_trytes(trytes, &mut bytes);
+    Ok(bytes)
+}
+
+/// Converts arbitrary slices of trytes to bytes, writing them into `bytes`.
+///
+/// Returns the number of bytes written.
+pub fn from_trytes_into(trytes: &[Tryte], bytes: &mut [Byte]) -> Result<usize, ConversionError> {
+    check_len_multiple_of(trytes.len(), 3)?;
+
+    let len = trytes.len() / 3 * 2;
+    check_buf_len(bytes.len(), len)?;
+
+    write_from_trytes(trytes, &mut bytes[..len]);
+    Ok(len)
+}
+
+/// Converts arbitrary slices of trytes to a `heapless::Vec` of bytes.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_trytes_heapless<const N: usize>(
+    trytes: &[Tryte],
+) -> Result<heapless::Vec<Byte, N>, ConversionError> {
+    into_heapless(|buf| from_trytes_into(trytes, buf))
+}
 
-    for i in 0..trytes.len() / 3 {
-        let t0 = trytes[3 * i];
-        let t1 = trytes[3 * i + 1];
-        let t2 = trytes[3 * i + 2];
+#[inline]
+pub(crate) fn write_from_trytes(trytes: &[Tryte], bytes: &mut [Byte]) {
+    for i in 0..bytes.len() / 2 {
+        let t0 = trytes[3 * i].0;
+        let t1 = trytes[3 * i + 1].0;
+        let t2 = trytes[3 * i + 2].0;
 
         let i0 = if t0 == TRYTE_9 { 0 } else { t0 - TRYTE_A + 1 };
         let i1 = if t1 == TRYTE_9 { 0 } else { t1 - TRYTE_A + 1 };
@@ -70,56 +166,90 @@ pub fn from_trytes(trytes: &[Tryte]) -> Vec<Byte> {
         bytes[2 * i] = i0 * 8 + i2 % 8;
         bytes[2 * i + 1] = i1 * 8 + i2 / 8;
     }
+}
+
+/// Converts an array of trits to an array of bytes.
+///
+/// `M` has to be `N / 9 * 2`, which is checked at compile time.
+pub fn from_trits_array<const N: usize, const M: usize>(trits: &[Trit; N]) -> [Byte; M] {
+    assert_len_ratio::<N, M, 2, 9>();
+
+    let mut bytes = [0_u8; M];
+    simd::trits_to_bytes(trits, &mut bytes);
     bytes
 }
 
 macro_rules! from_trits_conv {
-    ($func_name:ident, $length:expr) => {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
         /// Converts fixed-sized slices of trits to bytes.
         pub fn $func_name(trits: &[Trit]) -> [Byte; $length] {
-            #[cfg(not(feature = "no_checks"))]
-            {
-                assert_eq!(0, trits.len() % 9);
-                assert_eq!($length, trits.len() / 9 * 2);
-            }
-            let mut bytes = [0_u8; $length];
-
-            for i in 0..(trits.len() / 9) {
-                let i0 = trits[9 * i] + 3 * trits[9 * i + 1] + 9 * trits[9 * i + 2];
-                let i1 = trits[9 * i + 3] + 3 * trits[9 * i + 4] + 9 * trits[9 * i + 5];
-                let i2 = trits[9 * i + 6] + 3 * trits[9 * i + 7] + 9 * trits[9 * i + 8];
-
-                let j0 = if i0 < 0 { i0 + 27 } else { i0 } as u8;
-                let j1 = if i1 < 0 { i1 + 27 } else { i1 } as u8;
-                let j2 = if i2 < 0 { i2 + 27 } else { i2 } as u8;
+            from_trits_array::<{ $length / 2 * 9 }, $length>(as_array(trits).unwrap())
+        }
 
-                bytes[2 * i] = j0 * 8 + j2 % 8;
-                bytes[2 * i + 1] = j1 * 8 + j2 / 8;
-            }
-            bytes
+        /// Tries to convert fixed-sized slices of trits to bytes.
+        pub fn $try_func_name(trits: &[Trit]) -> Result<[Byte; $length], ConversionError> {
+            Ok(from_trits_array::<{ $length / 2 * 9 }, $length>(as_array(trits)?))
         }
     };
 }
 
-from_trits_conv!(from_trits_all, TRANSACTION_SIZE_BYTES);
-from_trits_conv!(from_trits_sig, SIG_MSG_FRG_SIZE_BYTES);
-from_trits_conv!(from_trits_243, 54);
-from_trits_conv!(from_trits_81, 18);
-from_trits_conv!(from_trits_27, 6);
+from_trits_conv!(from_trits_all, try_from_trits_all, TRANSACTION_SIZE_BYTES);
+from_trits_conv!(from_trits_sig, try_from_trits_sig, SIG_MSG_FRG_SIZE_BYTES);
+from_trits_conv!(from_trits_243, try_from_trits_243, 54);
+from_trits_conv!(from_trits_81, try_from_trits_81, 18);
+from_trits_conv!(from_trits_27, try_from_trits_27, 6);
 
 /// Converts fixed-sized slices of trits to bytes.
+#[cfg(feature = "alloc")]
 pub fn from_trits(trits: &[Trit]) -> Vec<Byte> {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert_eq!(0, trits.len() % 9);
+        check_len_multiple_of(trits.len(), 9).unwrap();
     }
 
     let mut bytes = vec![0_u8; trits.len() / 9 * 2];
+    write_from_trits(trits, &mut bytes);
+    bytes
+}
+
+/// Tries to convert arbitrary slices of trits to bytes.
+#[cfg(feature = "alloc")]
+pub fn try_from_trits(trits: &[Trit]) -> Result<Vec<Byte>, ConversionError> {
+    check_len_multiple_of(trits.len(), 9)?;
+
+    let mut bytes = vec![0_u8; trits.len() / 9 * 2];
+    write_from_trits(trits, &mut bytes);
+    Ok(bytes)
+}
+
+/// Converts arbitrary slices of trits to bytes, writing them into `bytes`.
+///
+/// Returns the number of bytes written.
+pub fn from_trits_into(trits: &[Trit], bytes: &mut [Byte]) -> Result<usize, ConversionError> {
+    check_len_multiple_of(trits.len(), 9)?;
+
+    let len = trits.len() / 9 * 2;
+    check_buf_len(bytes.len(), len)?;
+
+    write_from_trits(trits, &mut bytes[..len]);
+    Ok(len)
+}
+
+/// Converts arbitrary slices of trits to a `heapless::Vec` of bytes.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_trits_heapless<const N: usize>(
+    trits: &[Trit],
+) -> Result<heapless::Vec<Byte, N>, ConversionError> {
+    into_heapless(|buf| from_trits_into(trits, buf))
+}
 
-    for i in 0..(trits.len() / 9) {
-        let i0 = trits[9 * i] + 3 * trits[9 * i + 1] + 9 * trits[9 * i + 2];
-        let i1 = trits[9 * i + 3] + 3 * trits[9 * i + 4] + 9 * trits[9 * i + 5];
-        let i2 = trits[9 * i + 6] + 3 * trits[9 * i + 7] + 9 * trits[9 * i + 8];
+#[inline]
+pub(crate) fn write_from_trits(trits: &[Trit], bytes: &mut [Byte]) {
+    for i in 0..(bytes.len() / 2) {
+        let i0 = trits[9 * i] as i8 + 3 * trits[9 * i + 1] as i8 + 9 * trits[9 * i + 2] as i8;
+        let i1 = trits[9 * i + 3] as i8 + 3 * trits[9 * i + 4] as i8 + 9 * trits[9 * i + 5] as i8;
+        let i2 = trits[9 * i + 6] as i8 + 3 * trits[9 * i + 7] as i8 + 9 * trits[9 * i + 8] as i8;
 
         let j0 = if i0 < 0 { i0 + 27 } else { i0 } as u8;
         let j1 = if i1 < 0 { i1 + 27 } else { i1 } as u8;
@@ -128,75 +258,119 @@ pub fn from_trits(trits: &[Trit]) -> Vec<Byte> {
         bytes[2 * i] = j0 * 8 + j2 % 8;
         bytes[2 * i + 1] = j1 * 8 + j2 / 8;
     }
-    bytes
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const TRANSACTION: &str = "SEGQSWYCJHRLJYEGZLRYQAZPLVRAYIWGWJUMFFX99UZUKBQNFYAOQLOFARIKNEBKDRHJJWDJARXTNPHPAODJRSGJBVVYBVJHZALJWDCJHZRSACOVCVVAVHZVTPFTAJWVGFSVLSYXHNNXEGSMJHDBZKGFQNYJJJBAPDHFFGZ9POSOMWTDPGXI9KQRLMUVWNEQDANMXROVORJVALWVGDDJAFOOBXUKVCCIVXSSHZUCZV9XVBASLWX9NXPWGMGYCRD9ILQMKIGPBGGMKAIJKNALBLABATYFVIRBKTXTWNUZAUXRASB9EEIQHWBD9ZYUDBUPBSWXVYXQXECRCHQAYH9ZBUZBASPOIGBSGWJYFKFRITUBVMCYGCMAPTXOIWEVTUXSUOUPTUQOPMMPUTHXMOP9CW9THAZXEPMOMNEOBLUBPOAIOBEBERRZCIKHSTDWUSUPUWNJOCLNZDCEKWWAAJDPJXJEHHSYFN9MH9BGUDQ9CSZBIHRC9PSQJPGKH9ILZDWUWLEKWFKUFFFIMOQKRMKOYXEJHXLCEGCGGKHGJUHOXINSWCKRNMUNAJDCVLZGEBII9ASTYFTDYDZIZSNHIWHSQ9HODQMVNDKMKHCFDXIIGDIVJSBOOE9GRIXCD9ZUTWCUDKFTETSYSRBQABXCXZFOWQMQFXHYZWD9JZXUWHILMRNWXSGUMIIXZYCTWWHCWMSSTCNSQXQXMQPTM9MOQMIVDYNNARDCVNQEDTBKWOIOSKPKPOZHJGJJGNYWQWUWAZMBZJ9XEJMRVRYFQPJ9NOIIXEGIKMMN9DXYQUILRSCSJDIDN9DCTFGQIYWROZQIEQTKMRVLGGDGA9UVZPNRGSVTZYAPMWFUWDEUULSEEGAGITPJQ9DBEYEN9NVJPUWZTOTJHEQIXAPDOICBNNCJVDNM9YRNXMMPCOYHJDUFNCYTZGRCBZKOLHHUK9VOZWHEYQND9WUHDNGFTAS99MRCAU9QOYVUZKTIBDNAAPNEZBQPIRUFUMAWVTCXSXQQIYQPRFDUXCLJNMEIKVAINVCCZROEWEX9XVRM9IHLHQCKC9VLK9ZZWFBJUZKGJCSOPQPFVVAUDLKFJIJKMLZXFBMXLMWRSNDXRMMDLE9VBPUZB9SVLTMHA9DDDANOKIPY9ULDWAKOUDFEDHZDKMU9VMHUSFG9HRGZAZULEJJTEH9SLQDOMZTLVMBCXVNQPNKXRLBOUCCSBZRJCZIUFTFBKFVLKRBPDKLRLZSMMIQNMOZYFBGQFKUJYIJULGMVNFYJWPKPTSMYUHSUEXIPPPPPJTMDQLFFSFJFEPNUBDEDDBPGAOEJGQTHIWISLRDAABO9H9CSIAXPPJYCRFRCIH9TVBZKTCK9SPQZUYMUOKMZYOMPRHRGF9UAKZTZZG9VVVTIHMSNDREUOUOSLKUHTNFXTNSJVPVWCQXUDIMJIAMBPXUGBNDTBYPKYQYJJCDJSCTTWHOJKORLHGKRJMDCMRHSXHHMQBFJWZWHNUHZLYOAFQTRZFXDBYASYKWEVHKYDTJIAUKNCCEPSW9RITZXBOFKBAQOWHKTALQSCHARLUUGXISDMBVEUKOVXTKTEVKLGYVYHPNYWKNLCVETWIHHVTBWT9UPMTQWBZPRPRSISUBIBECVDNIZQULAGLONGVFLVZPBMHJND9CEVIXSYGFZAGGN9MQYOAKMENSEOGCUNKEJTDLEDCD9LGKYANHMZFSSDDZJKTKUJSFL9GYFDICTPJEPDSBXDQTARJQEWUVWDWSQPKIHPJONKHESSQH9FNQEO9WUCFDWPPPTIQPWCVDYTTWPLCJJVYNKE9ZEJNQBEJBMDBLNJKQDOQOHVS9VY9UPSU9KZVDFOESHNRRWBK9EZCYALAUYFGPCEWJQDXFENSNQEAUWDXJGOMCLQUQWMCPHOBZZ9SZJ9KZXSHDLPHPNYMVUJQSQETTN9SG9SIANJHWUYQXZXAJLYHCZYRGITZYQLAAYDVQVNKCDIYWAYBAFBMAYEAEAGMTJGJRSNHBHCEVIQRXEFVWJWOPU9FPDOWIFL9EWGHICRBNRITJDZNYACOGTUDBZYIYZZWAOCDBQFFNTTSTGKECWTVWZSPHX9HNRUYEAEWXENEIDLVVFMZFVPUNHMQPAIOKVIBDIHQIHFGRJOHHONPLGBSJUD9HHDTQQUZN9NVJYOAUMXMMOCNUFLZ9MXKZAGDGKVADXOVCAXEQYZGOGQKDLKIUPYXIL9PXYBQXGYDEGNXTFURSWQYLJDFKEV9VVBBQLTLHIBTFYBAJSZMDMPQHPWSFVWOJQDPHV9DYSQPIBL9LYZHQKKOVF9TFVTTXQEUWFQSLGLVTGK99VSUEDXIBIWCQHDQQSQLDHZ9999999999999999999TRINITY99999999999999999999TNXSQ9D99A99999999B99999999OGBHPUUHS9CKWSAPIMDIRNSUJ9CFPGKTUFAGQYVMFKOZSVAHIFJXWCFBZLICUWF9GNDZWCOWDUIIZ9999OXNRVXLBKJXEZMVABR9UQBVSTBDFSAJVRRNFEJRL9UFTOFPJHQMQKAJHDBIQAETS9OUVTQ9DSPAOZ9999TRINITY99999999999999999999LPZYMWQME999999999MMMMMMMMMDTIZE9999999999999999999999";
+    use crate::{error::ErrorKind, test_vectors::TRANSACTION};
 
     #[test]
     fn test_from_trytes_all() {
-        let tx = TRANSACTION.as_bytes();
+        let tx = crate::trytes::from_tryte_str(TRANSACTION);
 
-        assert_eq!(
-            tx,
-            &crate::trytes::from_bytes_all(&from_trytes_all(&tx[..])).to_vec()[..]
-        );
+        assert_eq!(tx, &crate::trytes::from_bytes_all(&from_trytes_all(&tx)).to_vec()[..]);
     }
 
     #[test]
     fn test_from_trytes_sig() {
-        let sig = &TRANSACTION.as_bytes()[0..2187];
+        let sig = &crate::trytes::from_tryte_str(TRANSACTION)[0..2187];
 
-        assert_eq!(
-            sig,
-            &crate::trytes::from_bytes_sig(&from_trytes_sig(sig)).to_vec()[..]
-        );
+        assert_eq!(sig, &crate::trytes::from_bytes_sig(&from_trytes_sig(sig)).to_vec()[..]);
     }
 
     #[test]
     fn test_from_trytes() {
-        let trytes = &TRANSACTION.as_bytes()[13..574];
+        let trytes = &crate::trytes::from_tryte_str(TRANSACTION)[13..574];
 
-        assert_eq!(
-            trytes,
-            &crate::trytes::from_bytes(&from_trytes(trytes)).to_vec()[..]
-        );
+        assert_eq!(trytes, &crate::trytes::from_bytes(&from_trytes(trytes)).to_vec()[..]);
     }
 
     #[test]
     fn test_from_trits_all() {
-        let tx = TRANSACTION.as_bytes();
+        let tx = crate::trytes::from_tryte_str(TRANSACTION);
         let trits = crate::trits::from_trytes(&tx);
 
-        assert_eq!(
-            tx,
-            &crate::trytes::from_bytes_all(&from_trits_all(&trits[..])).to_vec()[..]
-        );
+        assert_eq!(tx, &crate::trytes::from_bytes_all(&from_trits_all(&trits[..])).to_vec()[..]);
     }
 
     #[test]
     fn test_from_trits_sig() {
-        let sig = &TRANSACTION.as_bytes()[0..2187];
+        let sig = &crate::trytes::from_tryte_str(TRANSACTION)[0..2187];
         let trits = crate::trits::from_trytes(sig);
 
-        assert_eq!(
-            sig,
-            &crate::trytes::from_bytes_sig(&from_trits_sig(&trits)).to_vec()[..]
-        );
+        assert_eq!(sig, &crate::trytes::from_bytes_sig(&from_trits_sig(&trits)).to_vec()[..]);
     }
 
     #[test]
     fn test_from_trits() {
-        let trytes = &TRANSACTION.as_bytes()[13..574];
+        let trytes = &crate::trytes::from_tryte_str(TRANSACTION)[13..574];
         let trits = crate::trits::from_trytes(trytes);
 
-        assert_eq!(
-            trytes,
-            &crate::trytes::from_bytes(&from_trits(&trits)).to_vec()[..]
-        );
+        assert_eq!(trytes, &crate::trytes::from_bytes(&from_trits(&trits)).to_vec()[..]);
+    }
+
+    #[test]
+    fn test_try_from_trytes() {
+        let trytes = &crate::trytes::from_tryte_str(TRANSACTION)[13..574];
+        assert_eq!(from_trytes(trytes), try_from_trytes(trytes).unwrap());
+
+        let err = try_from_trytes(&crate::trytes::from_tryte_str("AB")).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(2, err.index());
+    }
+
+    #[test]
+    fn test_into() {
+        let trytes = &crate::trytes::from_tryte_str(&TRANSACTION[..81]);
+        let trits = crate::trits::from_trytes(trytes);
+        let mut bytes = [0_u8; 60];
+
+        assert_eq!(Ok(54), from_trytes_into(trytes, &mut bytes));
+        assert_eq!(from_trytes(trytes), bytes[..54]);
+
+        assert_eq!(Ok(54), from_trits_into(&trits, &mut bytes));
+        assert_eq!(from_trits(&trits), bytes[..54]);
+
+        let err = from_trits_into(&trits, &mut bytes[..53]).unwrap_err();
+        assert_eq!(ErrorKind::BufferTooSmall, err.kind());
+        assert_eq!(54, err.index());
+
+        let heapless_bytes = from_trytes_heapless::<54>(trytes).unwrap();
+        assert_eq!(from_trytes(trytes), &heapless_bytes[..]);
+    }
+
+    #[test]
+    fn test_try_from_trits_27() {
+        assert_eq!(from_trits_27(&[Trit::Pos; 27]), try_from_trits_27(&[Trit::Pos; 27]).unwrap());
+
+        let err = try_from_trits_27(&[Trit::Zero; 9]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(9, err.index());
+    }
+
+    #[test]
+    fn test_bytes92_encoding() {
+        let trits = crate::trits::from_tryte_str("RBTC9D9DCDEAKDCDFD9DSCFA");
+        let bytes = Bytes92::encode(&trits).unwrap();
+
+        assert_eq!(from_trits(&trits), bytes);
+        assert_eq!(trits, Bytes92::decode(&bytes, trits.len()).unwrap());
+
+        let err = Bytes92::decode(&bytes, trits.len() - 9).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+
+        let err = Bytes92::decode(&[0, 255], 9).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(0, err.index());
+
+        // The last pair of bytes is padded with zero trits.
+        let padded = Bytes92::encode(&trits[..10]).unwrap();
+        assert_eq!(4, padded.len());
+        assert_eq!(&trits[..10], &Bytes92::decode(&padded, 10).unwrap()[..]);
+
+        let err = Bytes92::decode(&bytes[..4], 10).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(2, err.index());
     }
 }
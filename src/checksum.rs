@@ -0,0 +1,152 @@
+//! Checksums of IOTA addresses.
+//!
+//! The checksum of an 81 tryte address are the last 9 trytes of its Kerl hash. Appending it
+//! gives a 90 tryte address, which protects against typos when addresses are passed around as
+//! tryte strings.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+
+use crate::{
+    error::ConversionError,
+    kerl::Kerl,
+    kerl::HASH_LENGTH,
+    trits,
+    trytes,
+    types::Trit,
+    types::Tryte,
+    util::check_len,
+    util::check_tryte_str,
+};
+
+/// The number of trytes of an address without checksum.
+pub const ADDRESS_LENGTH: usize = 81;
+
+/// The number of trytes of a checksum.
+pub const CHECKSUM_LENGTH: usize = 9;
+
+/// The number of trytes of an address with checksum.
+pub const ADDRESS_WITH_CHECKSUM_LENGTH: usize = ADDRESS_LENGTH + CHECKSUM_LENGTH;
+
+/// Computes the checksum of an address.
+pub fn checksum(address: &[Tryte; ADDRESS_LENGTH]) -> [Tryte; CHECKSUM_LENGTH] {
+    checksum_of_trits(&trits::from_trytes_array(address))
+}
+
+/// Appends the checksum to an 81 tryte address.
+///
+/// This function will panic if the address is not 81 trytes long.
+#[cfg(feature = "alloc")]
+pub fn add_checksum(address: &str) -> String {
+    try_add_checksum(address).unwrap()
+}
+
+/// Tries to append the checksum to an 81 tryte address.
+///
+/// Fails if the address is not 81 trytes long or contains characters other than trytes.
+#[cfg(feature = "alloc")]
+pub fn try_add_checksum(address: &str) -> Result<String, ConversionError> {
+    let checksum = checksum_of(address)?;
+
+    let mut address_with_checksum = String::with_capacity(ADDRESS_WITH_CHECKSUM_LENGTH);
+    address_with_checksum.push_str(address);
+    address_with_checksum.extend(checksum.iter().map(|&t| char::from(t)));
+    Ok(address_with_checksum)
+}
+
+/// Returns whether the last 9 trytes of a 90 tryte address are the checksum of the first 81.
+///
+/// Fails if the address is not 90 trytes long or contains characters other than trytes.
+pub fn validate(address: &str) -> Result<bool, ConversionError> {
+    check_len(address.len(), ADDRESS_WITH_CHECKSUM_LENGTH)?;
+    check_tryte_str(address)?;
+
+    let checksum = checksum_of(&address[..ADDRESS_LENGTH])?;
+    Ok(checksum.iter().map(|t| t.as_byte()).eq(address[ADDRESS_LENGTH..].bytes()))
+}
+
+/// Removes the checksum from a 90 tryte address.
+///
+/// The checksum is not validated, see `validate`. This function will panic if the address is not
+/// 90 trytes long.
+pub fn remove_checksum(address: &str) -> &str {
+    try_remove_checksum(address).unwrap()
+}
+
+/// Tries to remove the checksum from a 90 tryte address.
+///
+/// The checksum is not validated, see `validate`. Fails if the address is not 90 trytes long or
+/// contains characters other than trytes.
+pub fn try_remove_checksum(address: &str) -> Result<&str, ConversionError> {
+    check_len(address.len(), ADDRESS_WITH_CHECKSUM_LENGTH)?;
+    check_tryte_str(address)?;
+
+    Ok(&address[..ADDRESS_LENGTH])
+}
+
+fn checksum_of(address: &str) -> Result<[Tryte; CHECKSUM_LENGTH], ConversionError> {
+    check_len(address.len(), ADDRESS_LENGTH)?;
+
+    Ok(checksum_of_trits(&trits::try_from_tryte_str_array(address)?))
+}
+
+fn checksum_of_trits(address: &[Trit; HASH_LENGTH]) -> [Tryte; CHECKSUM_LENGTH] {
+    let mut kerl = Kerl::new();
+    let mut hash = [Trit::Zero; HASH_LENGTH];
+
+    kerl.absorb(address);
+    kerl.squeeze(&mut hash);
+
+    let hash: [Tryte; ADDRESS_LENGTH] = trytes::from_trits_array(&hash);
+    let mut checksum = [Tryte::default(); CHECKSUM_LENGTH];
+    checksum.copy_from_slice(&hash[ADDRESS_LENGTH - CHECKSUM_LENGTH..]);
+    checksum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    const ADDRESS: &str =
+        "UYEEERFQYTPFAHIPXDQAQYWYMSMCLMGBTYAXLWFRFFWPYFOICOVLK9A9VYNCKK9TQUNBTARCEQXJHD9VY";
+    const ADDRESS_WITH_CHECKSUM: &str =
+        "UYEEERFQYTPFAHIPXDQAQYWYMSMCLMGBTYAXLWFRFFWPYFOICOVLK9A9VYNCKK9TQUNBTARCEQXJHD9VYXOEDEOMRC";
+
+    #[test]
+    fn test_add_checksum() {
+        assert_eq!(ADDRESS_WITH_CHECKSUM, add_checksum(ADDRESS));
+        assert_eq!(
+            "RGVOWCDJAGSO9TNLBBPUVYE9KHBOAZNVFRVKVYYCHRKQRKRNKGGWBF9WCRJVROKLVKWZUMBABVJGAALWUNPJ9QIHFW",
+            add_checksum(
+                "RGVOWCDJAGSO9TNLBBPUVYE9KHBOAZNVFRVKVYYCHRKQRKRNKGGWBF9WCRJVROKLVKWZUMBABVJGAALWU"
+            )
+        );
+
+        let err = try_add_checksum(ADDRESS_WITH_CHECKSUM).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(90, err.index());
+
+        let err = try_add_checksum(&ADDRESS.replace('K', "k")).unwrap_err();
+        assert_eq!(ErrorKind::InvalidTryte, err.kind());
+        assert_eq!(52, err.index());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(Ok(true), validate(ADDRESS_WITH_CHECKSUM));
+        assert_eq!(Ok(false), validate(&ADDRESS_WITH_CHECKSUM.replace("XOEDEOMRC", "XOEDEOMRD")));
+        assert_eq!(Ok(false), validate(&ADDRESS_WITH_CHECKSUM.replacen('U', "V", 1)));
+
+        assert_eq!(ErrorKind::InvalidLength, validate(ADDRESS).unwrap_err().kind());
+        let err = validate(&ADDRESS_WITH_CHECKSUM.replace("XOEDEOMRC", "XOEDEOMR@")).unwrap_err();
+        assert_eq!(ErrorKind::InvalidTryte, err.kind());
+        assert_eq!(89, err.index());
+    }
+
+    #[test]
+    fn test_remove_checksum() {
+        assert_eq!(ADDRESS, remove_checksum(ADDRESS_WITH_CHECKSUM));
+        assert_eq!(ErrorKind::InvalidLength, try_remove_checksum(ADDRESS).unwrap_err().kind());
+    }
+}
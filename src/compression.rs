@@ -0,0 +1,219 @@
+//! Compression of transactions in the 9/2 byte encoding.
+//!
+//! The 1458 bytes of the signature or message fragment, see `bytes::from_trytes_sig`, are
+//! mostly `9` trytes, which are encoded as zero bytes. The compressed format drops the trailing
+//! zero bytes of the fragment:
+//!
+//! | bytes                   | content                                      |
+//! |-------------------------|----------------------------------------------|
+//! | 2                       | length `n` of the fragment, big-endian       |
+//! | `n`                     | the fragment without its trailing zero bytes |
+//! | 324                     | the rest of the transaction                  |
+//!
+//! A compressed transaction is between 326 and 1784 bytes long.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{
+    constants::SIG_MSG_FRG_SIZE_BYTES,
+    constants::TRANSACTION_SIZE_BYTES,
+    constants::TRANSACTION_SIZE_TRYTES,
+    error::ConversionError,
+    error::ErrorKind,
+    trytes,
+    types::Byte,
+    types::Tryte,
+    util::check_buf_len,
+    util::check_bytes,
+    util::check_len,
+    util::into_heapless,
+};
+
+/// The number of bytes of the length prefix.
+const PREFIX_LENGTH: usize = 2;
+
+/// The number of bytes following the fragment.
+const REST_LENGTH: usize = TRANSACTION_SIZE_BYTES - SIG_MSG_FRG_SIZE_BYTES;
+
+/// The maximum number of bytes of a compressed transaction.
+pub const MAX_COMPRESSED_LENGTH: usize = PREFIX_LENGTH + TRANSACTION_SIZE_BYTES;
+
+/// Compresses the 1782 bytes of a transaction.
+///
+/// This function will panic if the slice is not 1782 bytes long or contains invalid bytes.
+#[cfg(feature = "alloc")]
+pub fn compress(bytes: &[Byte]) -> Vec<Byte> {
+    try_compress(bytes).unwrap()
+}
+
+/// Tries to compress the 1782 bytes of a transaction.
+///
+/// Fails if the slice is not 1782 bytes long or contains invalid bytes.
+#[cfg(feature = "alloc")]
+pub fn try_compress(bytes: &[Byte]) -> Result<Vec<Byte>, ConversionError> {
+    let mut compressed = vec![0; MAX_COMPRESSED_LENGTH];
+    let len = compress_into(bytes, &mut compressed)?;
+    compressed.truncate(len);
+    Ok(compressed)
+}
+
+/// Compresses the 1782 bytes of a transaction, writing them into `compressed`.
+///
+/// Returns the number of bytes written.
+pub fn compress_into(bytes: &[Byte], compressed: &mut [Byte]) -> Result<usize, ConversionError> {
+    check_len(bytes.len(), TRANSACTION_SIZE_BYTES)?;
+    check_bytes(bytes)?;
+
+    let fragment = &bytes[..SIG_MSG_FRG_SIZE_BYTES];
+    let fragment_len = fragment.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    let len = PREFIX_LENGTH + fragment_len + REST_LENGTH;
+    check_buf_len(compressed.len(), len)?;
+
+    compressed[..PREFIX_LENGTH].copy_from_slice(&(fragment_len as u16).to_be_bytes());
+    compressed[PREFIX_LENGTH..PREFIX_LENGTH + fragment_len]
+        .copy_from_slice(&fragment[..fragment_len]);
+    compressed[PREFIX_LENGTH + fragment_len..len].copy_from_slice(&bytes[SIG_MSG_FRG_SIZE_BYTES..]);
+    Ok(len)
+}
+
+/// Compresses the 1782 bytes of a transaction to a `heapless::Vec`.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn compress_heapless<const N: usize>(
+    bytes: &[Byte],
+) -> Result<heapless::Vec<Byte, N>, ConversionError> {
+    into_heapless(|buf| compress_into(bytes, buf))
+}
+
+/// Decompresses a transaction to its 1782 bytes.
+///
+/// This function will panic if the compressed transaction is invalid.
+pub fn decompress(compressed: &[Byte]) -> [Byte; TRANSACTION_SIZE_BYTES] {
+    try_decompress(compressed).unwrap()
+}
+
+/// Tries to decompress a transaction to its 1782 bytes.
+///
+/// Fails if the length doesn't match the length prefix, if the prefix exceeds 1458 bytes, if the
+/// fragment ends in a zero byte or if the transaction contains invalid bytes. Since `compress`
+/// drops all trailing zero bytes, every transaction has a single valid compressed encoding. The
+/// index of an invalid byte refers to the decompressed bytes.
+pub fn try_decompress(
+    compressed: &[Byte],
+) -> Result<[Byte; TRANSACTION_SIZE_BYTES], ConversionError> {
+    if compressed.len() < PREFIX_LENGTH {
+        return Err(ConversionError::new(ErrorKind::InvalidLength, compressed.len()));
+    }
+
+    let fragment_len = u16::from_be_bytes([compressed[0], compressed[1]]) as usize;
+    if fragment_len > SIG_MSG_FRG_SIZE_BYTES {
+        return Err(ConversionError::new(ErrorKind::OutOfRange, 0));
+    }
+    check_len(compressed.len(), PREFIX_LENGTH + fragment_len + REST_LENGTH)?;
+    if fragment_len > 0 && compressed[PREFIX_LENGTH + fragment_len - 1] == 0 {
+        return Err(ConversionError::new(ErrorKind::OutOfRange, fragment_len - 1));
+    }
+
+    let mut bytes = [0; TRANSACTION_SIZE_BYTES];
+    bytes[..fragment_len].copy_from_slice(&compressed[PREFIX_LENGTH..PREFIX_LENGTH + fragment_len]);
+    bytes[SIG_MSG_FRG_SIZE_BYTES..].copy_from_slice(&compressed[PREFIX_LENGTH + fragment_len..]);
+
+    check_bytes(&bytes)?;
+    Ok(bytes)
+}
+
+/// Decompresses a transaction to its 2673 trytes.
+///
+/// This function will panic if the compressed transaction is invalid.
+pub fn decompress_trytes(compressed: &[Byte]) -> [Tryte; TRANSACTION_SIZE_TRYTES] {
+    try_decompress_trytes(compressed).unwrap()
+}
+
+/// Tries to decompress a transaction to its 2673 trytes.
+///
+/// Fails like `try_decompress`.
+pub fn try_decompress_trytes(
+    compressed: &[Byte],
+) -> Result<[Tryte; TRANSACTION_SIZE_TRYTES], ConversionError> {
+    Ok(trytes::from_bytes_all(&try_decompress(compressed)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bytes, test_vectors::TRANSACTION};
+
+    fn with_message(message: &str) -> [Byte; TRANSACTION_SIZE_BYTES] {
+        let tx = format!("{}{}{}", message, "9".repeat(2187 - message.len()), &TRANSACTION[2187..]);
+        bytes::from_trytes_all(&trytes::from_tryte_str(&tx))
+    }
+
+    #[test]
+    fn test_compress() {
+        // A full fragment only gains the length prefix.
+        let bytes = bytes::from_trytes_all(&trytes::from_tryte_str(TRANSACTION));
+        let compressed = compress(&bytes);
+        assert_eq!(MAX_COMPRESSED_LENGTH, compressed.len());
+        assert_eq!(&[5, 178], &compressed[..2]);
+        assert_eq!(&bytes[..], &compressed[2..]);
+
+        // The last triplet `A99` is encoded as `8, 0`, so its zero byte is dropped too.
+        let compressed = compress(&with_message("HELLOWORLA"));
+        assert_eq!(PREFIX_LENGTH + 7 + REST_LENGTH, compressed.len());
+        assert_eq!(&[0, 7], &compressed[..2]);
+
+        let compressed = compress(&with_message(""));
+        assert_eq!(PREFIX_LENGTH + REST_LENGTH, compressed.len());
+        assert_eq!(&[0, 0], &compressed[..2]);
+
+        let err = try_compress(&bytes[1..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(1781, err.index());
+
+        let mut buf = [0; 400];
+        assert_eq!(ErrorKind::BufferTooSmall, compress_into(&bytes, &mut buf).unwrap_err().kind());
+        assert_eq!(Ok(326), compress_into(&with_message(""), &mut buf));
+        assert_eq!(326, compress_heapless::<400>(&with_message("")).unwrap().len());
+    }
+
+    #[test]
+    fn test_decompress() {
+        for message in ["", "A", "HELLOWORLA", "99999999A99999999", &TRANSACTION[..2187]].iter() {
+            let bytes = with_message(message);
+            assert_eq!(&bytes[..], &decompress(&compress(&bytes))[..]);
+            assert_eq!(
+                &trytes::from_bytes_all(&bytes)[..],
+                &decompress_trytes(&compress(&bytes))[..]
+            );
+        }
+
+        let compressed = compress(&with_message("HELLOWORLA"));
+
+        let err = try_decompress(&compressed[..1]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(1, err.index());
+
+        let err = try_decompress(&compressed[1..]).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(0, err.index());
+
+        let err = try_decompress(&compressed[..compressed.len() - 1]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+
+        // The fragment keeps one of its trailing zero bytes.
+        let mut padded = vec![0, 8];
+        padded.extend_from_slice(&compressed[2..9]);
+        padded.push(0);
+        padded.extend_from_slice(&compressed[9..]);
+        let err = try_decompress(&padded).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(7, err.index());
+
+        let mut invalid = compressed.clone();
+        invalid[2] = 255;
+        let err = try_decompress_trytes(&invalid).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(0, err.index());
+    }
+}
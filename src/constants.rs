@@ -8,5 +8,8 @@ pub(crate) const TRANSACTION_SIZE_BYTES: usize = TRANSACTION_SIZE_TRITS / 9 * 2;
 
 pub(crate) const TRYTE_LENGTH_FOR_MAX_TOKEN_SUPPLY: usize = 11;
 pub(crate) const TRYTE_LENGTH_FOR_MAX_I64: usize = 13;
+pub(crate) const TRYTE_LENGTH_FOR_MAX_S129: usize = 27;
+pub(crate) const TRIT_LENGTH_FOR_MAX_S129: usize = TRYTE_LENGTH_FOR_MAX_S129 * 3; // 81
 
 pub(crate) const MAX_TRYTE_TRIPLET_ABS: i64 = 9841; // (3^9-1)/2
+pub(crate) const MAX_S129_TRITS_ABS: u128 = 221713244121518884974124815309574946401; // (3^81-1)/2
@@ -0,0 +1,183 @@
+//! The Curl-P sponge hash function.
+//!
+//! Curl-P has a state of 729 trits and absorbs and squeezes 243 trits at a time. The number of
+//! rounds of its transformation is configurable, IOTA uses Curl-P-81 to hash transactions and
+//! Curl-P-27 elsewhere.
+
+use crate::{
+    constants::TRANSACTION_SIZE_TRITS,
+    error::ConversionError,
+    types::Trit,
+    util::as_array,
+};
+
+/// The number of trits absorbed and squeezed at a time.
+pub const HASH_LENGTH: usize = 243;
+
+/// The number of trits of the state.
+pub const STATE_LENGTH: usize = HASH_LENGTH * 3;
+
+/// The number of rounds of Curl-P-27.
+pub const CURL_P_27: usize = 27;
+
+/// The number of rounds of Curl-P-81.
+pub const CURL_P_81: usize = 81;
+
+/// The result of the transformation, indexed by `a + 4 * b + 5` for two trits `a` and `b`.
+///
+/// Indices 3 and 7 can't be reached.
+const TRUTH_TABLE: [Trit; 11] = [
+    Trit::Pos,
+    Trit::Zero,
+    Trit::Neg,
+    Trit::Zero,
+    Trit::Pos,
+    Trit::Neg,
+    Trit::Zero,
+    Trit::Zero,
+    Trit::Neg,
+    Trit::Pos,
+    Trit::Zero,
+];
+
+/// The Curl-P sponge.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug)]
+pub struct Curl {
+    rounds: usize,
+    state: [Trit; STATE_LENGTH],
+}
+
+impl Default for Curl {
+    /// Creates a Curl-P-81 sponge.
+    fn default() -> Self {
+        Self::new(CURL_P_81)
+    }
+}
+
+impl Curl {
+    /// Creates a new sponge that transforms its state with `rounds` rounds.
+    pub fn new(rounds: usize) -> Self {
+        Self { rounds, state: [Trit::Zero; STATE_LENGTH] }
+    }
+
+    /// Resets the sponge to its initial state.
+    pub fn reset(&mut self) {
+        self.state = [Trit::Zero; STATE_LENGTH];
+    }
+
+    /// Absorbs trits in chunks of 243, the last chunk may be shorter.
+    pub fn absorb(&mut self, trits: &[Trit]) {
+        for chunk in trits.chunks(HASH_LENGTH) {
+            self.state[..chunk.len()].copy_from_slice(chunk);
+            self.transform();
+        }
+    }
+
+    /// Squeezes trits into `trits` in chunks of 243, the last chunk may be shorter.
+    pub fn squeeze(&mut self, trits: &mut [Trit]) {
+        for chunk in trits.chunks_mut(HASH_LENGTH) {
+            chunk.copy_from_slice(&self.state[..chunk.len()]);
+            self.transform();
+        }
+    }
+
+    fn transform(&mut self) {
+        let mut copy = [Trit::Zero; STATE_LENGTH];
+
+        for _ in 0..self.rounds {
+            copy.copy_from_slice(&self.state);
+            let mut index = 0;
+
+            for trit in self.state.iter_mut() {
+                let a = copy[index] as i8;
+                index = if index < 365 { index + 364 } else { index - 365 };
+                let b = copy[index] as i8;
+
+                *trit = TRUTH_TABLE[(a + 4 * b + 5) as usize];
+            }
+        }
+    }
+}
+
+/// Returns the Curl-P-81 hash of a transaction.
+pub fn transaction_hash(transaction: &[Trit; TRANSACTION_SIZE_TRITS]) -> [Trit; HASH_LENGTH] {
+    let mut curl = Curl::new(CURL_P_81);
+    let mut hash = [Trit::Zero; HASH_LENGTH];
+
+    curl.absorb(transaction);
+    curl.squeeze(&mut hash);
+    hash
+}
+
+/// Returns the Curl-P-81 hash of a transaction given as a slice.
+///
+/// Fails if the slice doesn't hold exactly 8019 trits.
+pub fn try_transaction_hash(transaction: &[Trit]) -> Result<[Trit; HASH_LENGTH], ConversionError> {
+    Ok(transaction_hash(as_array(transaction)?))
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::string::String;
+
+    use super::*;
+    use crate::{test_vectors::TRANSACTION, trits, trytes};
+
+    fn curl(input: &str, rounds: usize, output_len: usize) -> String {
+        let mut curl = Curl::new(rounds);
+        curl.absorb(&trits::from_tryte_str(input));
+
+        let mut output = vec![Trit::Zero; output_len * 3];
+        curl.squeeze(&mut output);
+
+        trytes::from_trits(&output).iter().map(|&t| char::from(t)).collect()
+    }
+
+    #[test]
+    fn test_curl_p_81() {
+        assert_eq!(
+            "TJVKPMTAMIZVBVHIVQUPTKEMPROEKV9SB9COEDQYRHYPTYSKQIAN9PQKMZHCPO9TS9BHCORFKW9CQXZEE",
+            curl("A", CURL_P_81, 81)
+        );
+    }
+
+    #[test]
+    fn test_curl_p_27() {
+        assert_eq!(
+            "AALINNWGBVCYC99RWCGJHSWWEPWGOFXBQIMHEENHANMXBWSOEBRDLPCWXQ9JLLNGL9GGZRI9HFYKXMRUX",
+            curl("A", CURL_P_27, 81)
+        );
+    }
+
+    #[test]
+    fn test_curl_multi_absorb_multi_squeeze() {
+        assert_eq!(
+            "CQNV9EWUDFTC9VPROHCUYCTCKCOYJXW9N9NGPJL9SODWEBOSQSIYZVQ9SKPPWACWAIHZQW9LKXLHOSXCT\
+             WVICETPZFXLQC9LKQXHBNKQCRYAGCYMFBQSOD9OBZVYRYOKGURYQTBXKEGUOCITRAPKRMCEBKHYDLNFHY",
+            curl(&"ABC".repeat(90), CURL_P_81, 162)
+        );
+    }
+
+    #[test]
+    fn test_transaction_hash() {
+        let transaction = trits::from_tryte_str_all(TRANSACTION);
+        let hash = transaction_hash(&transaction);
+
+        assert_eq!(
+            "ZTDPEOIAPRD9WLJKPWHIMTUANYHKPZOOFPFUD9EMUVNKYTCVTCREFJJUCNOUNQX9Z9KSJUTFYFMTNUMYD",
+            trytes::from_trits(&hash).iter().map(|&t| char::from(t)).collect::<String>()
+        );
+        assert_eq!(Ok(hash), try_transaction_hash(&transaction));
+        assert!(try_transaction_hash(&transaction[1..]).is_err());
+
+        let mut curl = Curl::default();
+        curl.absorb(&transaction);
+        curl.reset();
+        curl.absorb(&transaction);
+        let mut squeezed = [Trit::Zero; HASH_LENGTH];
+        curl.squeeze(&mut squeezed);
+        assert_eq!(hash, squeezed);
+    }
+}
@@ -0,0 +1,245 @@
+//! The `TritEncoding` trait implemented by the different ways of packing trits into bytes.
+//!
+//! | Encoding               | Trits per byte | Byte values                     |
+//! |------------------------|----------------|---------------------------------|
+//! | `t1b1::T1B1`           | 1              | `-1..=1` as `i8`                |
+//! | `t3b1::T3B1`           | 3              | tryte codes `0..=26`            |
+//! | `t4b1::T4B1`           | 4              | `-40..=40` as `i8`              |
+//! | `t5b1::T5B1`           | 5              | `-121..=121` as `i8`            |
+//! | `bytes::Bytes92`       | 4.5            | 9 trits per 2 bytes             |
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{
+    error::ConversionError,
+    error::ErrorKind,
+    types::Trit,
+    util::check_buf_len,
+    util::check_len,
+};
+
+/// A way of packing trits into bytes.
+///
+/// Since the padding of the last byte can't be told apart from encoded zero trits, decoding
+/// requires the number of trits that were encoded.
+pub trait TritEncoding {
+    /// Returns the number of bytes needed to encode `num_trits` trits.
+    fn packed_len(num_trits: usize) -> usize;
+
+    /// Encodes the trits, writing the bytes into `bytes`.
+    ///
+    /// Returns the number of bytes written.
+    fn encode_into(trits: &[Trit], bytes: &mut [u8]) -> Result<usize, ConversionError>;
+
+    /// Decodes `num_trits` trits from the bytes, writing them into `trits`.
+    ///
+    /// Returns the number of trits written.
+    fn decode_into(
+        bytes: &[u8],
+        num_trits: usize,
+        trits: &mut [Trit],
+    ) -> Result<usize, ConversionError>;
+
+    /// Encodes the trits.
+    #[cfg(feature = "alloc")]
+    fn encode(trits: &[Trit]) -> Result<Vec<u8>, ConversionError> {
+        let mut bytes = vec![0; Self::packed_len(trits.len())];
+        let len = Self::encode_into(trits, &mut bytes)?;
+        bytes.truncate(len);
+        Ok(bytes)
+    }
+
+    /// Decodes `num_trits` trits from the bytes.
+    #[cfg(feature = "alloc")]
+    fn decode(bytes: &[u8], num_trits: usize) -> Result<Vec<Trit>, ConversionError> {
+        let mut trits = vec![Trit::Zero; num_trits];
+        Self::decode_into(bytes, num_trits, &mut trits)?;
+        Ok(trits)
+    }
+}
+
+/// An encoding packing `K` trits into every byte as a balanced ternary number, which is stored as
+/// a two's complement `i8`. If the number of trits is not a multiple of `K`, the last byte is padded
+/// with zero trits.
+///
+/// `t1b1::T1B1`, `t4b1::T4B1` and `t5b1::T5B1` are instances of it.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Balanced<const K: usize>;
+
+impl<const K: usize> Balanced<K> {
+    /// Returns the number of bytes needed to pack `num_trits` trits.
+    pub const fn packed_len(num_trits: usize) -> usize {
+        num_trits.div_ceil(K)
+    }
+
+    /// Encodes arbitrary slices of trits.
+    #[cfg(feature = "alloc")]
+    pub fn from_trits(trits: &[Trit]) -> Vec<u8> {
+        let mut packed = vec![0; Self::packed_len(trits.len())];
+        pack_balanced::<_, K>(trits.iter().cloned(), &mut packed);
+        packed
+    }
+
+    /// Encodes arbitrary slices of trits, writing the bytes into `packed`.
+    ///
+    /// Returns the number of bytes written.
+    pub fn from_trits_into(trits: &[Trit], packed: &mut [u8]) -> Result<usize, ConversionError> {
+        let len = Self::packed_len(trits.len());
+        check_buf_len(packed.len(), len)?;
+
+        pack_balanced::<_, K>(trits.iter().cloned(), &mut packed[..len]);
+        Ok(len)
+    }
+
+    /// Decodes `num_trits` trits.
+    ///
+    /// This function will panic if `packed` doesn't hold exactly `num_trits` trits.
+    #[cfg(feature = "alloc")]
+    pub fn to_trits(packed: &[u8], num_trits: usize) -> Vec<Trit> {
+        #[cfg(not(feature = "no_checks"))]
+        {
+            Self::check_packed(packed, num_trits).unwrap();
+        }
+
+        unpack_balanced::<K>(packed).take(num_trits).collect()
+    }
+
+    /// Tries to decode `num_trits` trits.
+    #[cfg(feature = "alloc")]
+    pub fn try_to_trits(packed: &[u8], num_trits: usize) -> Result<Vec<Trit>, ConversionError> {
+        Self::check_packed(packed, num_trits)?;
+
+        Ok(unpack_balanced::<K>(packed).take(num_trits).collect())
+    }
+
+    /// Decodes `num_trits` trits, writing them into `trits`.
+    ///
+    /// Returns the number of trits written.
+    pub fn to_trits_into(
+        packed: &[u8],
+        num_trits: usize,
+        trits: &mut [Trit],
+    ) -> Result<usize, ConversionError> {
+        Self::check_packed(packed, num_trits)?;
+        check_buf_len(trits.len(), num_trits)?;
+
+        trits[..num_trits].iter_mut().zip(unpack_balanced::<K>(packed)).for_each(|(t, u)| *t = u);
+        Ok(num_trits)
+    }
+
+    /// Makes sure `packed` holds exactly `num_trits` trits and only valid bytes.
+    ///
+    /// The trits padding the last byte have to be zero, so every sequence of trits has a single
+    /// encoding.
+    pub(crate) fn check_packed(packed: &[u8], num_trits: usize) -> Result<(), ConversionError> {
+        check_len(packed.len(), Self::packed_len(num_trits))?;
+
+        if let Some(index) = packed.iter().position(|&b| !fits_trits(b, K)) {
+            return Err(ConversionError::new(ErrorKind::OutOfRange, index));
+        }
+
+        let last_trits = num_trits % K;
+        match packed.last() {
+            Some(&b) if last_trits != 0 && !fits_trits(b, last_trits) => {
+                Err(ConversionError::new(ErrorKind::OutOfRange, packed.len() - 1))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<const K: usize> TritEncoding for Balanced<K> {
+    fn packed_len(num_trits: usize) -> usize {
+        Balanced::<K>::packed_len(num_trits)
+    }
+
+    fn encode_into(trits: &[Trit], bytes: &mut [u8]) -> Result<usize, ConversionError> {
+        Balanced::<K>::from_trits_into(trits, bytes)
+    }
+
+    fn decode_into(
+        bytes: &[u8],
+        num_trits: usize,
+        trits: &mut [Trit],
+    ) -> Result<usize, ConversionError> {
+        Balanced::<K>::to_trits_into(bytes, num_trits, trits)
+    }
+}
+
+/// Packs `K` trits per byte as a balanced ternary number, least significant trit first, padding the
+/// last byte with zero trits.
+#[inline]
+pub(crate) fn pack_balanced<I, const K: usize>(mut trits: I, packed: &mut [u8])
+where
+    I: Iterator<Item = Trit>,
+{
+    for byte in packed.iter_mut() {
+        let chunk: [Trit; K] = take_trits(&mut trits);
+        *byte = chunk.iter().rev().fold(0_i8, |value, &t| value * 3 + t as i8) as u8;
+    }
+}
+
+/// Unpacks `K` trits per byte, see `pack_balanced`.
+#[inline]
+pub(crate) fn unpack_balanced<const K: usize>(packed: &[u8]) -> impl Iterator<Item = Trit> + '_ {
+    packed.iter().flat_map(|&b| unpack_balanced_byte::<K>(b))
+}
+
+#[inline]
+fn unpack_balanced_byte<const K: usize>(byte: u8) -> [Trit; K] {
+    let mut value = byte as i8;
+    let mut trits = [Trit::Zero; K];
+
+    for trit in trits.iter_mut() {
+        *trit = match value.rem_euclid(3) {
+            1 => Trit::Pos,
+            2 => Trit::Neg,
+            _ => Trit::Zero,
+        };
+        value = (value - *trit as i8) / 3;
+    }
+
+    trits
+}
+
+/// Returns whether the byte is a balanced ternary number of at most `num_trits` trits.
+#[inline]
+fn fits_trits(byte: u8, num_trits: usize) -> bool {
+    // The largest absolute value of n trits, (3^n-1)/2.
+    i16::from(byte as i8).abs() <= (3_i16.pow(num_trits as u32) - 1) / 2
+}
+
+/// Takes the next `K` trits, filling up with zero trits if there are less.
+#[inline]
+pub(crate) fn take_trits<I: Iterator<Item = Trit>, const K: usize>(trits: &mut I) -> [Trit; K] {
+    let mut taken = [Trit::Zero; K];
+    taken.iter_mut().zip(trits).for_each(|(t, u)| *t = u);
+    taken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bytes::Bytes92, t1b1::T1B1, t3b1::T3B1, t4b1::T4B1, t5b1::T5B1};
+
+    fn round_trip<E: TritEncoding>() {
+        for len in 0..30 {
+            let trits: Vec<Trit> =
+                (0..len).map(|i| [Trit::Neg, Trit::Pos, Trit::Zero, Trit::Pos][i % 4]).collect();
+
+            let bytes = E::encode(&trits).unwrap();
+            assert_eq!(E::packed_len(len), bytes.len());
+            assert_eq!(trits, E::decode(&bytes, len).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip::<T1B1>();
+        round_trip::<T3B1>();
+        round_trip::<T4B1>();
+        round_trip::<T5B1>();
+        round_trip::<Bytes92>();
+    }
+}
@@ -0,0 +1,73 @@
+//! The error type returned by the fallible `try_*` converter functions.
+
+use core::fmt;
+
+/// The kind of problem that made a conversion fail.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// A tryte that is not part of the tryte alphabet `9A-Z`.
+    InvalidTryte,
+    /// A trit that is not one of `-1`, `0` or `1`.
+    InvalidTrit,
+    /// An input whose length doesn't fit the conversion.
+    InvalidLength,
+    /// A value that can't be represented by the target type.
+    OutOfRange,
+    /// A character or decoded byte that is not ASCII.
+    NonAscii,
+    /// An output buffer that is too small to hold the converted values.
+    BufferTooSmall,
+    /// A character that is not a decimal digit.
+    InvalidDigit,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            ErrorKind::InvalidTryte => "invalid tryte",
+            ErrorKind::InvalidTrit => "invalid trit",
+            ErrorKind::InvalidLength => "invalid length",
+            ErrorKind::OutOfRange => "value out of range",
+            ErrorKind::NonAscii => "non-ASCII character",
+            ErrorKind::BufferTooSmall => "output buffer too small",
+            ErrorKind::InvalidDigit => "invalid digit",
+        };
+        f.write_str(description)
+    }
+}
+
+/// An error that occurred while converting between two datatypes.
+///
+/// Besides its kind, the error reports the index into the input at which the conversion failed.
+/// For `ErrorKind::InvalidLength` the index is the length of the input, for
+/// `ErrorKind::BufferTooSmall` it is the length the output buffer needs to have.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ConversionError {
+    kind: ErrorKind,
+    index: usize,
+}
+
+impl ConversionError {
+    pub(crate) fn new(kind: ErrorKind, index: usize) -> Self {
+        Self { kind, index }
+    }
+
+    /// Returns the kind of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the index into the input at which the conversion failed.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at index {}", self.kind, self.index)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConversionError {}
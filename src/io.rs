@@ -0,0 +1,623 @@
+//! `std::io` adapters that convert between tryte text and the 9/2 byte encoding while streaming.
+//!
+//! The adapters are named after the format of the stream they wrap:
+//!
+//! * `TryteReader` reads tryte text and yields bytes in the 9/2 encoding
+//! * `TryteWriter` accepts bytes in the 9/2 encoding and writes tryte text
+//! * `Bytes92Reader` reads bytes in the 9/2 encoding and yields tryte text
+//! * `Bytes92Writer` accepts tryte text and writes bytes in the 9/2 encoding
+//!
+//! Line breaks in tryte text are skipped, so dumps with one transaction per line can be read
+//! directly, and `TryteWriter::with_line_length` writes them. Triplets of trytes and pairs of
+//! bytes may be split across reads and writes, incomplete ones are carried over.
+//!
+//! Invalid input fails with an `io::Error` of kind `InvalidData`, whose inner error is a
+//! `ConversionError` with the byte offset into the stream as its index. A stream that ends within
+//! a triplet or pair fails with `ErrorKind::InvalidLength` and the length of the stream. Readers
+//! report this at the end of the stream, writers when calling `finish`.
+//!
+//! If the wrapped writer fails, a writer doesn't take in the input it was given, so the write can
+//! be retried. Output that was converted but not written yet is kept and written first by the next
+//! call of `write` or `flush`.
+//!
+//! ```
+//! use std::io::{Read, Write};
+//! use trinary::io::{TryteReader, TryteWriter};
+//!
+//! let dump = "HELLOWORLD99\nABCDEFGHIJKL\n";
+//!
+//! let mut bytes = Vec::new();
+//! TryteReader::new(dump.as_bytes()).read_to_end(&mut bytes).unwrap();
+//! assert_eq!(16, bytes.len());
+//!
+//! let mut writer = TryteWriter::with_line_length(Vec::new(), 12);
+//! writer.write_all(&bytes).unwrap();
+//! assert_eq!(dump.as_bytes(), &writer.finish().unwrap()[..]);
+//! ```
+
+use std::io::{self, Read, Write};
+
+use crate::{bytes, error::ConversionError, error::ErrorKind, trytes, types::Byte, types::Tryte};
+
+/// The number of bytes read from the wrapped reader at once.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Reads tryte text and yields the bytes of its trytes in the 9/2 encoding.
+#[derive(Debug)]
+pub struct TryteReader<R> {
+    inner: R,
+    chunk: Vec<u8>,
+    trytes: Vec<Tryte>,
+    bytes: Vec<Byte>,
+    pos: usize,
+    offset: usize,
+    eof: bool,
+    error: Option<ConversionError>,
+}
+
+impl<R: Read> TryteReader<R> {
+    /// Creates a reader of the tryte text of `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            chunk: vec![0; CHUNK_SIZE],
+            trytes: Vec::new(),
+            bytes: Vec::new(),
+            pos: 0,
+            offset: 0,
+            eof: false,
+            error: None,
+        }
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        let n = read_chunk(&mut self.inner, &mut self.chunk)?;
+        if n == 0 {
+            self.eof = true;
+            if !self.trytes.is_empty() {
+                self.error = Some(ConversionError::new(ErrorKind::InvalidLength, self.offset));
+            }
+            return Ok(());
+        }
+
+        if let Err(err) = push_tryte_text(&self.chunk[..n], self.offset, &mut self.trytes) {
+            self.error = Some(err);
+            return Ok(());
+        }
+        self.offset += n;
+
+        let complete = self.trytes.len() / 3 * 3;
+        self.bytes.resize(complete / 3 * 2, 0);
+        bytes::from_trytes_into(&self.trytes[..complete], &mut self.bytes).unwrap();
+        self.trytes.drain(..complete);
+        self.pos = 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for TryteReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.bytes.len() {
+            if let Some(err) = self.error {
+                return Err(invalid_data(err));
+            }
+            if self.eof {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+
+        Ok(copy_buffered(&self.bytes, &mut self.pos, buf, |&b| b))
+    }
+}
+
+/// Accepts bytes in the 9/2 encoding and writes their trytes as text.
+///
+/// Call `finish` after the last write, which fails if the bytes ended within a pair.
+#[derive(Debug)]
+pub struct TryteWriter<W: Write> {
+    inner: W,
+    pending: Vec<Byte>,
+    trytes: Vec<Tryte>,
+    out: Vec<u8>,
+    offset: usize,
+    line_length: usize,
+    column: usize,
+}
+
+impl<W: Write> TryteWriter<W> {
+    /// Creates a writer of tryte text to `inner`, without line breaks.
+    pub fn new(inner: W) -> Self {
+        Self::with_line_length(inner, 0)
+    }
+
+    /// Creates a writer of tryte text to `inner` that ends every line after `line_length` trytes.
+    ///
+    /// A line length of zero writes no line breaks.
+    pub fn with_line_length(inner: W, line_length: usize) -> Self {
+        Self {
+            inner,
+            pending: Vec::new(),
+            trytes: Vec::new(),
+            out: Vec::new(),
+            offset: 0,
+            line_length,
+            column: 0,
+        }
+    }
+
+    /// Flushes the writer and returns the wrapped writer.
+    ///
+    /// Fails if the bytes written ended within a pair.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.pending.is_empty() {
+            return Err(invalid_data(ConversionError::new(ErrorKind::InvalidLength, self.offset)));
+        }
+
+        self.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for TryteWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        write_out(&mut self.inner, &mut self.out)?;
+
+        let len = self.pending.len();
+        let base = self.offset - len;
+        self.pending.extend_from_slice(buf);
+
+        let complete = self.pending.len() / 2 * 2;
+        self.trytes.resize(complete / 2 * 3, Tryte::default());
+        if let Err(err) = trytes::from_bytes_into(&self.pending[..complete], &mut self.trytes) {
+            self.pending.truncate(len);
+            return Err(invalid_data(ConversionError::new(err.kind(), base + err.index())));
+        }
+
+        let mut column = self.column;
+        for tryte in self.trytes.iter() {
+            self.out.push(tryte.as_byte());
+            column += 1;
+            if column == self.line_length {
+                self.out.push(b'\n');
+                column = 0;
+            }
+        }
+
+        if let Err(err) = write_new_out(&mut self.inner, &mut self.out) {
+            self.pending.truncate(len);
+            return Err(err);
+        }
+        self.pending.drain(..complete);
+        self.offset += buf.len();
+        self.column = column;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        write_out(&mut self.inner, &mut self.out)?;
+        self.inner.flush()
+    }
+}
+
+/// Reads bytes in the 9/2 encoding and yields their trytes as text.
+#[derive(Debug)]
+pub struct Bytes92Reader<R> {
+    inner: R,
+    chunk: Vec<u8>,
+    pending: Vec<Byte>,
+    trytes: Vec<Tryte>,
+    pos: usize,
+    offset: usize,
+    eof: bool,
+    error: Option<ConversionError>,
+}
+
+impl<R: Read> Bytes92Reader<R> {
+    /// Creates a reader of the 9/2 encoded bytes of `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            chunk: vec![0; CHUNK_SIZE],
+            pending: Vec::new(),
+            trytes: Vec::new(),
+            pos: 0,
+            offset: 0,
+            eof: false,
+            error: None,
+        }
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        let n = read_chunk(&mut self.inner, &mut self.chunk)?;
+        if n == 0 {
+            self.eof = true;
+            if !self.pending.is_empty() {
+                self.error = Some(ConversionError::new(ErrorKind::InvalidLength, self.offset));
+            }
+            return Ok(());
+        }
+
+        let base = self.offset - self.pending.len();
+        self.pending.extend_from_slice(&self.chunk[..n]);
+        self.offset += n;
+
+        let complete = self.pending.len() / 2 * 2;
+        self.trytes.resize(complete / 2 * 3, Tryte::default());
+        self.pos = 0;
+        if let Err(err) = trytes::from_bytes_into(&self.pending[..complete], &mut self.trytes) {
+            // Nothing of the failed chunk is yielded.
+            self.trytes.clear();
+            self.error = Some(ConversionError::new(err.kind(), base + err.index()));
+            return Ok(());
+        }
+        self.pending.drain(..complete);
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Bytes92Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.trytes.len() {
+            if let Some(err) = self.error {
+                return Err(invalid_data(err));
+            }
+            if self.eof {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+
+        Ok(copy_buffered(&self.trytes, &mut self.pos, buf, |t| t.as_byte()))
+    }
+}
+
+/// Accepts tryte text and writes its trytes as bytes in the 9/2 encoding.
+///
+/// Call `finish` after the last write, which fails if the trytes ended within a triplet.
+#[derive(Debug)]
+pub struct Bytes92Writer<W: Write> {
+    inner: W,
+    pending: Vec<Tryte>,
+    out: Vec<Byte>,
+    offset: usize,
+}
+
+impl<W: Write> Bytes92Writer<W> {
+    /// Creates a writer of 9/2 encoded bytes to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { inner, pending: Vec::new(), out: Vec::new(), offset: 0 }
+    }
+
+    /// Flushes the writer and returns the wrapped writer.
+    ///
+    /// Fails if the trytes written ended within a triplet.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.pending.is_empty() {
+            return Err(invalid_data(ConversionError::new(ErrorKind::InvalidLength, self.offset)));
+        }
+
+        self.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for Bytes92Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        write_out(&mut self.inner, &mut self.out)?;
+
+        let len = self.pending.len();
+        push_tryte_text(buf, self.offset, &mut self.pending).map_err(invalid_data)?;
+
+        let complete = self.pending.len() / 3 * 3;
+        self.out.resize(complete / 3 * 2, 0);
+        bytes::from_trytes_into(&self.pending[..complete], &mut self.out).unwrap();
+
+        if let Err(err) = write_new_out(&mut self.inner, &mut self.out) {
+            self.pending.truncate(len);
+            return Err(err);
+        }
+        self.pending.drain(..complete);
+        self.offset += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        write_out(&mut self.inner, &mut self.out)?;
+        self.inner.flush()
+    }
+}
+
+fn invalid_data(err: ConversionError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+fn read_chunk<R: Read>(inner: &mut R, chunk: &mut [u8]) -> io::Result<usize> {
+    loop {
+        match inner.read(chunk) {
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            result => return result,
+        }
+    }
+}
+
+/// Writes `out` to `inner`, removing what was written, until it is empty or writing fails.
+fn write_out<W: Write>(inner: &mut W, out: &mut Vec<u8>) -> io::Result<()> {
+    let mut written = 0;
+    let result = write_prefix(inner, out, &mut written);
+    out.drain(..written);
+    result
+}
+
+/// Writes the output of a call of `write`, which takes in its input unless nothing was written.
+///
+/// On failure `out` is cleared, so the input can be converted again when the write is retried.
+fn write_new_out<W: Write>(inner: &mut W, out: &mut Vec<u8>) -> io::Result<()> {
+    let mut written = 0;
+    match write_prefix(inner, out, &mut written) {
+        Err(err) if written == 0 => {
+            out.clear();
+            Err(err)
+        }
+        // The rest is written by the next call of `write` or `flush`.
+        _ => {
+            out.drain(..written);
+            Ok(())
+        }
+    }
+}
+
+fn write_prefix<W: Write>(inner: &mut W, out: &[u8], written: &mut usize) -> io::Result<()> {
+    while *written < out.len() {
+        match inner.write(&out[*written..]) {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(n) => *written += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+/// Appends the trytes of `text`, which starts at `offset` of the stream, skipping line breaks.
+///
+/// On failure `trytes` is left unchanged.
+fn push_tryte_text(
+    text: &[u8],
+    offset: usize,
+    trytes: &mut Vec<Tryte>,
+) -> Result<(), ConversionError> {
+    let len = trytes.len();
+
+    for (i, &c) in text.iter().enumerate() {
+        if c == b'\n' || c == b'\r' {
+            continue;
+        }
+        match Tryte::from_byte(c) {
+            Some(tryte) => trytes.push(tryte),
+            None => {
+                trytes.truncate(len);
+                return Err(ConversionError::new(ErrorKind::InvalidTryte, offset + i));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn copy_buffered<T, F: Fn(&T) -> u8>(
+    buffered: &[T],
+    pos: &mut usize,
+    buf: &mut [u8],
+    to_byte: F,
+) -> usize {
+    let n = buf.len().min(buffered.len() - *pos);
+    for (b, t) in buf.iter_mut().zip(buffered[*pos..*pos + n].iter()) {
+        *b = to_byte(t);
+    }
+    *pos += n;
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = "HELLOWORLD99\nABCDEFGHIJKL\nMMMMMMNNNNNN\n";
+
+    /// A reader that returns a single byte per read, so every triplet and pair is split.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    /// A writer that accepts at most 5 bytes per write and fails every other write.
+    #[derive(Default)]
+    struct Flaky {
+        written: Vec<u8>,
+        calls: usize,
+    }
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.calls += 1;
+            if self.calls.is_multiple_of(2) {
+                return Err(io::Error::other("flaky"));
+            }
+            let n = buf.len().min(5);
+            self.written.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Writes `input` in chunks of `chunk_size`, retrying every failed write.
+    fn write_retrying<W: Write>(writer: &mut W, input: &[u8], chunk_size: usize) {
+        for mut chunk in input.chunks(chunk_size) {
+            while !chunk.is_empty() {
+                match writer.write(chunk) {
+                    Ok(n) => chunk = &chunk[n..],
+                    Err(err) => assert_eq!(io::ErrorKind::Other, err.kind()),
+                }
+            }
+        }
+        while writer.flush().is_err() {}
+    }
+
+    fn dump_bytes() -> Vec<Byte> {
+        bytes::from_trytes(&trytes::from_tryte_str(&DUMP.replace('\n', "")))
+    }
+
+    fn conversion_error(err: io::Error) -> ConversionError {
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        *err.get_ref().unwrap().downcast_ref::<ConversionError>().unwrap()
+    }
+
+    /// Reads until the first error, unlike `read_to_end` keeping the bytes read before it.
+    fn read_until_error<R: Read>(mut reader: R) -> (Vec<u8>, io::Error) {
+        let mut read = Vec::new();
+        let mut buf = [0; 100];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => panic!("no error"),
+                Ok(n) => read.extend_from_slice(&buf[..n]),
+                Err(err) => return (read, err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_tryte_reader() {
+        let mut bytes = Vec::new();
+        TryteReader::new(Trickle(DUMP.as_bytes())).read_to_end(&mut bytes).unwrap();
+        assert_eq!(dump_bytes(), bytes);
+
+        let mut bytes = Vec::new();
+        TryteReader::new(DUMP.as_bytes()).read_to_end(&mut bytes).unwrap();
+        assert_eq!(dump_bytes(), bytes);
+
+        let mut reader = TryteReader::new(Trickle(b"HELLO\nwORLD"));
+        let err = conversion_error(reader.read_to_end(&mut Vec::new()).unwrap_err());
+        assert_eq!(ErrorKind::InvalidTryte, err.kind());
+        assert_eq!(6, err.index());
+
+        let mut reader = TryteReader::new(&b"HELLOWORLD"[..]);
+        let err = conversion_error(reader.read_to_end(&mut Vec::new()).unwrap_err());
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(10, err.index());
+        // The error is reported again.
+        assert!(reader.read(&mut [0; 16]).is_err());
+    }
+
+    #[test]
+    fn test_tryte_writer() {
+        let mut writer = TryteWriter::with_line_length(Vec::new(), 12);
+        for b in dump_bytes() {
+            writer.write_all(&[b]).unwrap();
+        }
+        assert_eq!(DUMP.as_bytes(), &writer.finish().unwrap()[..]);
+
+        let mut writer = TryteWriter::new(Vec::new());
+        writer.write_all(&dump_bytes()).unwrap();
+        assert_eq!(DUMP.replace('\n', "").as_bytes(), &writer.finish().unwrap()[..]);
+
+        let mut writer = TryteWriter::new(Vec::new());
+        writer.write_all(&[0, 0, 0]).unwrap();
+        let err = conversion_error(writer.write(&[27 * 8]).unwrap_err());
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(3, err.index());
+
+        let err = conversion_error(writer.finish().unwrap_err());
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(3, err.index());
+    }
+
+    #[test]
+    fn test_tryte_writer_retry() {
+        for chunk_size in 1..8 {
+            let mut writer = TryteWriter::with_line_length(Flaky::default(), 12);
+            write_retrying(&mut writer, &dump_bytes(), chunk_size);
+            assert_eq!(DUMP.as_bytes(), &writer.finish().unwrap().written[..]);
+        }
+    }
+
+    #[test]
+    fn test_bytes92_reader() {
+        let bytes = dump_bytes();
+        let mut text = String::new();
+        Bytes92Reader::new(Trickle(&bytes)).read_to_string(&mut text).unwrap();
+        assert_eq!(DUMP.replace('\n', ""), text);
+
+        let mut reader = Bytes92Reader::new(Trickle(&[0, 0, 0, 27 * 8]));
+        let err = conversion_error(reader.read_to_end(&mut Vec::new()).unwrap_err());
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(3, err.index());
+
+        // The invalid byte follows a full chunk, which was yielded.
+        let mut bytes_chunk = vec![0; CHUNK_SIZE];
+        bytes_chunk.extend_from_slice(&[0, 0, 0, 27 * 8]);
+        let (text, err) = read_until_error(Bytes92Reader::new(&bytes_chunk[..]));
+        assert_eq!(CHUNK_SIZE / 2 * 3, text.len());
+        assert_eq!(CHUNK_SIZE + 3, conversion_error(err).index());
+
+        // A complete pair followed by single bytes.
+        let (text, err) =
+            read_until_error(Bytes92Reader::new((&[0, 0][..]).chain(Trickle(&[0, 216]))));
+        assert_eq!(b"999", &text[..]);
+        assert_eq!(3, conversion_error(err).index());
+
+        let mut reader = Bytes92Reader::new(&bytes[..5]);
+        let err = conversion_error(reader.read_to_end(&mut Vec::new()).unwrap_err());
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(5, err.index());
+    }
+
+    #[test]
+    fn test_bytes92_writer() {
+        let mut writer = Bytes92Writer::new(Vec::new());
+        for c in DUMP.bytes() {
+            writer.write_all(&[c]).unwrap();
+        }
+        assert_eq!(dump_bytes(), writer.finish().unwrap());
+
+        let mut writer = Bytes92Writer::new(Vec::new());
+        writer.write_all(b"AB").unwrap();
+        let err = conversion_error(writer.write(b"C\nd").unwrap_err());
+        assert_eq!(ErrorKind::InvalidTryte, err.kind());
+        assert_eq!(4, err.index());
+
+        let err = conversion_error(writer.finish().unwrap_err());
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(2, err.index());
+    }
+
+    #[test]
+    fn test_bytes92_writer_retry() {
+        for chunk_size in 1..8 {
+            let mut writer = Bytes92Writer::new(Flaky::default());
+            write_retrying(&mut writer, DUMP.as_bytes(), chunk_size);
+            assert_eq!(dump_bytes(), writer.finish().unwrap().written);
+        }
+    }
+}
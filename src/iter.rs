@@ -0,0 +1,327 @@
+//! Iterator adapters that convert lazily, without materializing the whole output.
+//!
+//! The adapters are provided by the `IteratorExt` extension trait:
+//!
+//! * `trits` converts bytes in the 9/2 encoding to trits
+//! * `trytes` converts trits to trytes
+//! * `bytes_9_2` converts trytes to bytes in the 9/2 encoding
+//!
+//! Every adapter consumes its input in groups, 2 bytes, 3 trits or 3 trytes. Their items are
+//! `Result`s, since the input may contain invalid bytes or end with an incomplete group. An
+//! incomplete group yields an `InvalidLength` error whose index is the length of the input, an
+//! invalid byte an `OutOfRange` error whose index is the position of the byte. The adapters end
+//! after the first error.
+//!
+//! ```
+//! use trinary::{bytes, iter::IteratorExt, trits, trytes};
+//!
+//! let trytes = trytes::from_tryte_str("HELLOWORLDHELLOWORLD999");
+//! let bytes = bytes::from_trytes(&trytes[..21]);
+//!
+//! // Only the first 3 pairs of bytes are converted.
+//! let head: Result<Vec<_>, _> = bytes.iter().copied().trits().take(27).collect();
+//! assert_eq!(Ok(trits::from_trytes(&trytes[..9])), head);
+//!
+//! let err = trytes.iter().copied().bytes_9_2().find_map(Result::err).unwrap();
+//! assert_eq!(23, err.index());
+//! ```
+
+use core::iter::FusedIterator;
+
+use crate::{
+    bytes::write_from_trytes,
+    error::ConversionError,
+    error::ErrorKind,
+    trits::write_from_bytes,
+    trytes::from_trit_triplet,
+    types::Byte,
+    types::Trit,
+    types::Tryte,
+    util::check_bytes,
+};
+
+/// Extension trait providing the lazy converters of this module for every iterator.
+pub trait IteratorExt: Iterator + Sized {
+    /// Converts bytes in the 9/2 encoding to trits, 9 trits per 2 bytes.
+    fn trits(self) -> TritsIter<Self>
+    where
+        Self: Iterator<Item = Byte>,
+    {
+        TritsIter { iter: self, buf: [Trit::Zero; 9], pos: 9, index: 0, done: false }
+    }
+
+    /// Converts trits to trytes, one tryte per 3 trits.
+    fn trytes(self) -> TrytesIter<Self>
+    where
+        Self: Iterator<Item = Trit>,
+    {
+        TrytesIter { iter: self, index: 0, done: false }
+    }
+
+    /// Converts trytes to bytes in the 9/2 encoding, 2 bytes per 3 trytes.
+    fn bytes_9_2(self) -> Bytes92Iter<Self>
+    where
+        Self: Iterator<Item = Tryte>,
+    {
+        Bytes92Iter { iter: self, buf: [0; 2], pos: 2, index: 0, done: false }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+/// Iterator over the trits of bytes in the 9/2 encoding, see `IteratorExt::trits`.
+#[derive(Clone, Debug)]
+pub struct TritsIter<I> {
+    iter: I,
+    buf: [Trit; 9],
+    pos: usize,
+    index: usize,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Byte>> Iterator for TritsIter<I> {
+    type Item = Result<Trit, ConversionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos < self.buf.len() {
+            self.pos += 1;
+            return Some(Ok(self.buf[self.pos - 1]));
+        }
+        if self.done {
+            return None;
+        }
+
+        let mut pair = [0; 2];
+        match read_group(&mut self.iter, &mut pair, &mut self.index) {
+            Ok(false) => {
+                self.done = true;
+                return None;
+            }
+            Ok(true) => (),
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+
+        if let Err(err) = check_bytes(&pair) {
+            self.done = true;
+            return Some(Err(ConversionError::new(err.kind(), self.index - 2 + err.index())));
+        }
+
+        write_from_bytes(&pair, &mut self.buf);
+        self.pos = 1;
+        Some(Ok(self.buf[0]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buf.len() - self.pos;
+        if self.done {
+            return (buffered, Some(buffered));
+        }
+
+        // An invalid pair ends the iterator after a single error, so only one more item is sure.
+        let (lower, upper) = self.iter.size_hint();
+        (buffered + usize::from(lower >= 1), upper.map(|u| buffered + u / 2 * 9 + u % 2))
+    }
+}
+
+impl<I: Iterator<Item = Byte>> FusedIterator for TritsIter<I> {}
+
+/// Iterator over the trytes of trits, see `IteratorExt::trytes`.
+#[derive(Clone, Debug)]
+pub struct TrytesIter<I> {
+    iter: I,
+    index: usize,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Trit>> Iterator for TrytesIter<I> {
+    type Item = Result<Tryte, ConversionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut triplet = [Trit::Zero; 3];
+        match read_group(&mut self.iter, &mut triplet, &mut self.index) {
+            Ok(true) => Some(Ok(from_trit_triplet(triplet))),
+            Ok(false) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        (lower / 3, upper.map(|u| u.div_ceil(3)))
+    }
+}
+
+impl<I: Iterator<Item = Trit>> FusedIterator for TrytesIter<I> {}
+
+/// Iterator over the bytes in the 9/2 encoding of trytes, see `IteratorExt::bytes_9_2`.
+#[derive(Clone, Debug)]
+pub struct Bytes92Iter<I> {
+    iter: I,
+    buf: [Byte; 2],
+    pos: usize,
+    index: usize,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Tryte>> Iterator for Bytes92Iter<I> {
+    type Item = Result<Byte, ConversionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos < self.buf.len() {
+            self.pos += 1;
+            return Some(Ok(self.buf[self.pos - 1]));
+        }
+        if self.done {
+            return None;
+        }
+
+        let mut triplet = [Tryte::default(); 3];
+        match read_group(&mut self.iter, &mut triplet, &mut self.index) {
+            Ok(true) => {
+                write_from_trytes(&triplet, &mut self.buf);
+                self.pos = 1;
+                Some(Ok(self.buf[0]))
+            }
+            Ok(false) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buf.len() - self.pos;
+        if self.done {
+            return (buffered, Some(buffered));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        (buffered + lower / 3 * 2, upper.map(|u| buffered + u.div_ceil(3) * 2))
+    }
+}
+
+impl<I: Iterator<Item = Tryte>> FusedIterator for Bytes92Iter<I> {}
+
+/// Fills `group` from the iterator, counting the consumed items in `index`.
+///
+/// Returns `false` if the iterator ended before the group, and fails if it ended within it.
+#[inline]
+fn read_group<T, I: Iterator<Item = T>>(
+    iter: &mut I,
+    group: &mut [T],
+    index: &mut usize,
+) -> Result<bool, ConversionError> {
+    for (i, item) in group.iter_mut().enumerate() {
+        match iter.next() {
+            Some(next) => *item = next,
+            None if i == 0 => return Ok(false),
+            None => return Err(ConversionError::new(ErrorKind::InvalidLength, *index)),
+        }
+        *index += 1;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::{bytes, trits, trytes};
+
+    const TRYTES: &str =
+        "SEGQSWYCJHRLJYEGZLRYQAZPLVRAYIWGWJUMFFX99UZUKBQNFYAOQLOFARIKNEBKDRHJJWDJARXTNPHPA";
+
+    #[test]
+    fn test_trits() {
+        let bytes = bytes::from_trytes(&trytes::from_tryte_str(TRYTES));
+        let trits: Result<Vec<_>, _> = bytes.iter().copied().trits().collect();
+        assert_eq!(Ok(trits::from_tryte_str(TRYTES)), trits);
+        assert_eq!((1, Some(243)), bytes.iter().copied().trits().size_hint());
+
+        // The first pair is invalid, so the only item is the error.
+        let iter = [255_u8, 0].iter().copied().trits();
+        let (lower, upper) = iter.size_hint();
+        let count = iter.count();
+        assert!(lower <= count && count <= upper.unwrap());
+        assert_eq!(1, count);
+
+        let err = bytes[..53].iter().copied().trits().last().unwrap().unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(53, err.index());
+
+        let mut invalid = bytes.clone();
+        invalid[5] = 27 * 8;
+        let mut iter = invalid.iter().copied().trits();
+        assert_eq!(18, iter.by_ref().take_while(Result::is_ok).count());
+        let err = invalid.iter().copied().trits().find_map(Result::err).unwrap();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(5, err.index());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_trytes() {
+        let trits = trits::from_tryte_str(TRYTES);
+        let trytes: Result<Vec<_>, _> = trits.iter().copied().trytes().collect();
+        assert_eq!(Ok(trytes::from_tryte_str(TRYTES)), trytes);
+
+        // Only the first tryte is converted.
+        let mut iter = trits.iter().copied().trytes();
+        assert_eq!(Some(Ok(trytes::from_tryte_str("S")[0])), iter.next());
+        assert_eq!((80, Some(80)), iter.size_hint());
+
+        let mut iter = trits[..4].iter().copied().trytes();
+        assert!(iter.next().unwrap().is_ok());
+        let err = iter.next().unwrap().unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(4, err.index());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_bytes_9_2() {
+        let trytes = trytes::from_tryte_str(TRYTES);
+        let bytes: Result<Vec<_>, _> = trytes.iter().copied().bytes_9_2().collect();
+        assert_eq!(Ok(bytes::from_trytes(&trytes)), bytes);
+        assert_eq!((54, Some(54)), trytes.iter().copied().bytes_9_2().size_hint());
+
+        let err = trytes[..80].iter().copied().bytes_9_2().find_map(Result::err).unwrap();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(80, err.index());
+
+        // Round trip through all adapters.
+        let roundtrip: Result<Vec<_>, _> = trytes
+            .iter()
+            .copied()
+            .bytes_9_2()
+            .map(Result::unwrap)
+            .trits()
+            .map(Result::unwrap)
+            .trytes()
+            .collect();
+        assert_eq!(Ok(trytes), roundtrip);
+    }
+}
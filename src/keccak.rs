@@ -0,0 +1,172 @@
+//! A minimal Keccak-384 implementation for Kerl.
+//!
+//! This is the original Keccak with the padding byte `0x01`, not the standardized SHA3-384 whose
+//! padding byte is `0x06`.
+
+/// The number of bytes absorbed per permutation, (1600 - 2 * 384) / 8.
+const RATE: usize = 104;
+
+/// The number of bytes of a digest.
+pub(crate) const DIGEST_LEN: usize = 48;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000_0000_0000_0001,
+    0x0000_0000_0000_8082,
+    0x8000_0000_0000_808a,
+    0x8000_0000_8000_8000,
+    0x0000_0000_0000_808b,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8009,
+    0x0000_0000_0000_008a,
+    0x0000_0000_0000_0088,
+    0x0000_0000_8000_8009,
+    0x0000_0000_8000_000a,
+    0x0000_0000_8000_808b,
+    0x8000_0000_0000_008b,
+    0x8000_0000_0000_8089,
+    0x8000_0000_0000_8003,
+    0x8000_0000_0000_8002,
+    0x8000_0000_0000_0080,
+    0x0000_0000_0000_800a,
+    0x8000_0000_8000_000a,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8080,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8008,
+];
+
+/// The rotation offsets of the combined rho and pi steps, in the order of `PI_LANES`.
+const RHO_OFFSETS: [u32; 24] =
+    [1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44];
+
+/// The lanes visited by the pi step, starting at lane 1.
+const PI_LANES: [usize; 24] =
+    [10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1];
+
+/// The Keccak-384 sponge, whose lane `x + 5 * y` is `state[x + 5 * y]`.
+#[derive(Clone, Debug)]
+pub(crate) struct Keccak384 {
+    state: [u64; 25],
+    buffer: [u8; RATE],
+    buffered: usize,
+}
+
+impl Keccak384 {
+    pub(crate) fn new() -> Self {
+        Self { state: [0; 25], buffer: [0; RATE], buffered: 0 }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub(crate) fn update(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            let len = (RATE - self.buffered).min(bytes.len());
+            self.buffer[self.buffered..self.buffered + len].copy_from_slice(&bytes[..len]);
+            self.buffered += len;
+            bytes = &bytes[len..];
+
+            if self.buffered == RATE {
+                self.absorb_buffer();
+            }
+        }
+    }
+
+    /// Pads the absorbed bytes and returns the digest.
+    ///
+    /// The sponge has to be reset before it can be used again.
+    pub(crate) fn finalize(&mut self) -> [u8; DIGEST_LEN] {
+        self.buffer[self.buffered..].iter_mut().for_each(|b| *b = 0);
+        self.buffer[self.buffered] |= 0x01;
+        self.buffer[RATE - 1] |= 0x80;
+        self.absorb_buffer();
+
+        let mut digest = [0_u8; DIGEST_LEN];
+        for (chunk, lane) in digest.chunks_exact_mut(8).zip(self.state.iter()) {
+            chunk.copy_from_slice(&lane.to_le_bytes());
+        }
+        digest
+    }
+
+    fn absorb_buffer(&mut self) {
+        for (lane, chunk) in self.state.iter_mut().zip(self.buffer.chunks_exact(8)) {
+            let mut bytes = [0_u8; 8];
+            bytes.copy_from_slice(chunk);
+            *lane ^= u64::from_le_bytes(bytes);
+        }
+
+        keccak_f(&mut self.state);
+        self.buffered = 0;
+    }
+}
+
+/// The Keccak-f[1600] permutation.
+fn keccak_f(state: &mut [u64; 25]) {
+    for round_constant in ROUND_CONSTANTS.iter() {
+        // Theta
+        let mut columns = [0_u64; 5];
+        for (x, column) in columns.iter_mut().enumerate() {
+            *column = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for (i, lane) in state.iter_mut().enumerate() {
+            let x = i % 5;
+            *lane ^= columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+        }
+
+        // Rho and pi
+        let mut last = state[1];
+        for (&lane, &offset) in PI_LANES.iter().zip(RHO_OFFSETS.iter()) {
+            let next = state[lane];
+            state[lane] = last.rotate_left(offset);
+            last = next;
+        }
+
+        // Chi
+        for row in state.chunks_exact_mut(5) {
+            let copy = [row[0], row[1], row[2], row[3], row[4]];
+            for (x, lane) in row.iter_mut().enumerate() {
+                *lane = copy[x] ^ (!copy[(x + 1) % 5] & copy[(x + 2) % 5]);
+            }
+        }
+
+        // Iota
+        state[0] ^= round_constant;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(bytes: &[u8]) -> [u8; DIGEST_LEN] {
+        let mut keccak = Keccak384::new();
+        keccak.update(bytes);
+        keccak.finalize()
+    }
+
+    #[test]
+    fn test_keccak384() {
+        assert_eq!(
+            [
+                0x2c, 0x23, 0x14, 0x6a, 0x63, 0xa2, 0x9a, 0xcf, 0x99, 0xe7, 0x3b, 0x88, 0xf8, 0xc2,
+                0x4e, 0xaa, 0x7d, 0xc6, 0x0a, 0xa7, 0x71, 0x78, 0x0c, 0xcc, 0x00, 0x6a, 0xfb, 0xfa,
+                0x8f, 0xe2, 0x47, 0x9b, 0x2d, 0xd2, 0xb2, 0x13, 0x62, 0x33, 0x74, 0x41, 0xac, 0x12,
+                0xb5, 0x15, 0x91, 0x19, 0x57, 0xff
+            ],
+            digest(b"")
+        );
+
+        // Spans two blocks.
+        assert_eq!(
+            [
+                0xd3, 0xb9, 0xda, 0x96, 0x73, 0x6d, 0xcf, 0x26, 0x21, 0x94, 0x75, 0x94, 0xa5, 0x2f,
+                0x13, 0xb2, 0xdd, 0xd8, 0x54, 0x7c, 0x48, 0x22, 0xab, 0x78, 0x68, 0xc0, 0x27, 0xe1,
+                0x70, 0xf6, 0x5b, 0x4b, 0xee, 0x13, 0x3e, 0xed, 0x90, 0x2c, 0x34, 0xf7, 0x33, 0x22,
+                0x7c, 0xbe, 0x5c, 0x94, 0x78, 0x04
+            ],
+            digest(&[b'a'; 200])
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! The Kerl hash function, which hashes trits with Keccak-384.
+//!
+//! Every chunk of 243 trits is converted to a 384 bit integer by `words::trits_243_to_bytes_48`
+//! and absorbed by the Keccak sponge. Squeezing converts the digest back to 243 trits and feeds
+//! its bitwise complement into the reset sponge, so further chunks can be squeezed.
+
+use core::convert::TryInto;
+
+use crate::{
+    error::ConversionError,
+    keccak::Keccak384,
+    types::Trit,
+    util::check_len_multiple_of,
+    words::bytes_48_to_trits_243,
+    words::trits_243_to_bytes_48,
+};
+
+/// The number of trits of a hash, and the chunk size of `absorb` and `squeeze`.
+pub const HASH_LENGTH: usize = 243;
+
+/// The Kerl sponge.
+// Copying a sponge by accident would silently fork its state.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug)]
+pub struct Kerl {
+    keccak: Keccak384,
+}
+
+impl Default for Kerl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Kerl {
+    /// Creates a new sponge.
+    pub fn new() -> Self {
+        Self { keccak: Keccak384::new() }
+    }
+
+    /// Resets the sponge to its initial state.
+    pub fn reset(&mut self) {
+        self.keccak.reset();
+    }
+
+    /// Absorbs trits, whose length has to be a multiple of 243.
+    ///
+    /// The last trit of every chunk is ignored, see `words`.
+    ///
+    /// This function will panic if the length is not a multiple of 243.
+    pub fn absorb(&mut self, trits: &[Trit]) {
+        #[cfg(not(feature = "no_checks"))]
+        {
+            check_len_multiple_of(trits.len(), HASH_LENGTH).unwrap();
+        }
+
+        self.absorb_inner(trits);
+    }
+
+    /// Tries to absorb trits.
+    ///
+    /// Fails if the length is not a multiple of 243.
+    pub fn try_absorb(&mut self, trits: &[Trit]) -> Result<(), ConversionError> {
+        check_len_multiple_of(trits.len(), HASH_LENGTH)?;
+
+        self.absorb_inner(trits);
+        Ok(())
+    }
+
+    /// Squeezes trits into `trits`, whose length has to be a multiple of 243.
+    ///
+    /// The last trit of every chunk is zero.
+    ///
+    /// This function will panic if the length is not a multiple of 243.
+    pub fn squeeze(&mut self, trits: &mut [Trit]) {
+        #[cfg(not(feature = "no_checks"))]
+        {
+            check_len_multiple_of(trits.len(), HASH_LENGTH).unwrap();
+        }
+
+        self.squeeze_inner(trits);
+    }
+
+    /// Tries to squeeze trits into `trits`.
+    ///
+    /// Fails if the length is not a multiple of 243.
+    pub fn try_squeeze(&mut self, trits: &mut [Trit]) -> Result<(), ConversionError> {
+        check_len_multiple_of(trits.len(), HASH_LENGTH)?;
+
+        self.squeeze_inner(trits);
+        Ok(())
+    }
+
+    /// Returns the hash of trits, whose length has to be a multiple of 243.
+    pub fn hash(trits: &[Trit]) -> Result<[Trit; HASH_LENGTH], ConversionError> {
+        let mut kerl = Self::new();
+        let mut hash = [Trit::Zero; HASH_LENGTH];
+
+        kerl.try_absorb(trits)?;
+        kerl.squeeze_inner(&mut hash);
+        Ok(hash)
+    }
+
+    fn absorb_inner(&mut self, trits: &[Trit]) {
+        for chunk in trits.chunks_exact(HASH_LENGTH) {
+            self.keccak.update(&trits_243_to_bytes_48(chunk.try_into().unwrap()));
+        }
+    }
+
+    fn squeeze_inner(&mut self, trits: &mut [Trit]) {
+        for chunk in trits.chunks_exact_mut(HASH_LENGTH) {
+            let mut bytes = self.keccak.finalize();
+            chunk.copy_from_slice(&bytes_48_to_trits_243(&bytes));
+
+            self.keccak.reset();
+            bytes.iter_mut().for_each(|b| *b = !*b);
+            self.keccak.update(&bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::string::String;
+
+    use super::*;
+    use crate::{error::ErrorKind, trits, trytes};
+
+    fn kerl(input: &str, output_len: usize) -> String {
+        let mut kerl = Kerl::new();
+        kerl.absorb(&trits::from_tryte_str(input));
+
+        let mut output = vec![Trit::Zero; output_len * 3];
+        kerl.squeeze(&mut output);
+
+        trytes::from_trits(&output).iter().map(|&t| char::from(t)).collect()
+    }
+
+    #[test]
+    fn test_kerl_one_absorb() {
+        assert_eq!(
+            "OXJCNFHUNAHWDLKKPELTBFUCVW9KLXKOGWERKTJXQMXTKFKNWNNXYD9DMJJABSEIONOSJTTEVKVDQEWTW",
+            kerl(
+                "GYOMKVTSNHVJNCNFBBAH9AAMXLPLLLROQY99QN9DLSJUHDPBLCFFAIQXZA9BKMBJCYSFHFPXAHDWZFEIZ",
+                81
+            )
+        );
+    }
+
+    #[test]
+    fn test_kerl_multi_squeeze() {
+        assert_eq!(
+            "EJEAOOZYSAWFPZQESYDHZCGYNSTWXUMVJOVDWUNZJXDGWCLUFGIMZRMGCAZGKNPLBRLGUNYWKLJTYEAQX\
+             IRSENBEGRNZWSVUMWDHWCTEOUYZZWHGETVOUOUIEYGQWXFUESSTMJZVMFTCCTCXMQJYWYKTGKHPWHVCHW",
+            kerl(
+                "EMIDYNHBWMBCXVDEFOFWINXTERALUKYYPPHKP9JJFGJEIUY9MUDVNFZHMMWZUYUSWAIOWEVTHNWMHANBH",
+                162
+            )
+        );
+    }
+
+    #[test]
+    fn test_kerl_multi_absorb_multi_squeeze() {
+        assert_eq!(
+            "LUCKQVACOGBFYSPPVSSOXJEKNSQQRQKPZC9NXFSMQNRQCGGUL9OHVVKBDSKEQEBKXRNUJSRXYVHJTXBPD\
+             WQGNSCDCBAIRHAQCOWZEBSNHIJIGPZQITIBJQ9LNTDIBTCQ9EUWKHFLGFUVGGUWJONK9GBCDUIMAYMMQX",
+            kerl(
+                "G9JYBOMPUXHYHKSNRNMMSSZCSHOFYOYNZRSZMAAYWDYEIMVVOGKPJBVBM9TDPULSFUNMTVXRKFIDOHUXX\
+                 VYDLFSZYZTWQYTE9SPYYWYTXJYQ9IFGYOLZXWZBKWZN9QOOTBQMWMUBLEWUEEASRHRTNIQWJQNDWRYLCA",
+                162
+            )
+        );
+    }
+
+    #[test]
+    fn test_kerl_hash() {
+        let input = trits::from_tryte_str(
+            "GYOMKVTSNHVJNCNFBBAH9AAMXLPLLLROQY99QN9DLSJUHDPBLCFFAIQXZA9BKMBJCYSFHFPXAHDWZFEIZ",
+        );
+        let mut expected = [Trit::Zero; HASH_LENGTH];
+        let mut kerl = Kerl::new();
+        kerl.absorb(&input);
+        kerl.squeeze(&mut expected);
+
+        assert_eq!(Ok(expected), Kerl::hash(&input));
+
+        let err = Kerl::hash(&input[..242]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(242, err.index());
+        assert!(kerl.try_squeeze(&mut expected[..3]).is_err());
+    }
+}
@@ -1,5 +1,13 @@
 //! This library provides certain functions to convert between trits, trytes, bytes, tryte strings,
 //! ASCII text and signed 64 bit numbers.
+//!
+//! Every converter that can fail on malformed input has a `try_*` counterpart that returns a
+//! `Result<_, ConversionError>` instead of panicking.
+//!
+//! Converters returning a `Vec` or `String` require the `alloc` feature, which is enabled by
+//! `std`. Without it the fixed-size converters, the `*_into` converters and the `*_heapless`
+//! converters returning `heapless::Vec`/`heapless::String` are still available, so the crate can
+//! be used with `default-features = false` on targets without an allocator.
 
 #![deny(
     warnings,
@@ -16,26 +24,47 @@
 #![allow(dead_code)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 #[macro_use]
 extern crate alloc;
 
 #[macro_use]
-extern crate lazy_static;
-
-#[cfg(not(feature = "std"))]
-pub(crate) mod std {
-    pub use core::*;
-}
+mod macros;
 
 mod constants;
+mod keccak;
 mod luts;
+// The SIMD kernels need `core::arch` intrinsics, which are unsafe to call.
+#[allow(unsafe_code)]
+mod simd;
+#[cfg(test)]
+mod test_vectors;
 mod util;
 
 pub mod ascii_strings;
+#[cfg(feature = "alloc")]
+pub mod big_ternary;
+#[cfg(feature = "alloc")]
+pub mod buffers;
 pub mod bytes;
+pub mod checksum;
+pub mod compression;
+pub mod curl;
+pub mod encoding;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod iter;
+pub mod kerl;
+pub mod normalize;
 pub mod numbers;
+pub mod t1b1;
+pub mod t3b1;
+pub mod t4b1;
+pub mod t5b1;
+pub mod transaction;
 pub mod trits;
 pub mod tryte_strings;
 pub mod trytes;
 pub mod types;
+pub mod words;
@@ -1,38 +1,38 @@
 use crate::{types::Trit, types::Tryte};
 
-use heapless::{consts::U32, FnvIndexMap, IndexMap as HashMap};
-
-use core::iter::FromIterator;
+const N: Trit = Trit::Neg;
+const Z: Trit = Trit::Zero;
+const P: Trit = Trit::Pos;
 
 // LUT-SIZE: 3 Bytes * 27 = 81 Byte
 pub(crate) const TRYTE_CODE_TO_TRITS: [[Trit; 3]; 27] = [
-    [0, 0, 0],    //  0 => 0
-    [1, 0, 0],    //  1 => 1
-    [-1, 1, 0],   //  2 => 2
-    [0, 1, 0],    //  3 => 3
-    [1, 1, 0],    //  4 => 4
-    [-1, -1, 1],  //  5 => 5
-    [0, -1, 1],   //  6 => 6
-    [1, -1, 1],   //  7 => 7
-    [-1, 0, 1],   //  8 => 8
-    [0, 0, 1],    //  9 => 9
-    [1, 0, 1],    // 10 => 10
-    [-1, 1, 1],   // 11 => 11
-    [0, 1, 1],    // 12 => 12
-    [1, 1, 1],    // 13 => 13
-    [-1, -1, -1], // 14 => -13
-    [0, -1, -1],  // 15 => -12
-    [1, -1, -1],  // 16 => -11
-    [-1, 0, -1],  // 17 => -10
-    [0, 0, -1],   // 18 => -9
-    [1, 0, -1],   // 19 => -8
-    [-1, 1, -1],  // 20 => -7
-    [0, 1, -1],   // 21 => -6
-    [1, 1, -1],   // 22 => -5
-    [-1, -1, 0],  // 23 => -4
-    [0, -1, 0],   // 24 => -3
-    [1, -1, 0],   // 25 => -2
-    [-1, 0, 0],   // 26 => -1
+    [Z, Z, Z], //  0 => 0
+    [P, Z, Z], //  1 => 1
+    [N, P, Z], //  2 => 2
+    [Z, P, Z], //  3 => 3
+    [P, P, Z], //  4 => 4
+    [N, N, P], //  5 => 5
+    [Z, N, P], //  6 => 6
+    [P, N, P], //  7 => 7
+    [N, Z, P], //  8 => 8
+    [Z, Z, P], //  9 => 9
+    [P, Z, P], // 10 => 10
+    [N, P, P], // 11 => 11
+    [Z, P, P], // 12 => 12
+    [P, P, P], // 13 => 13
+    [N, N, N], // 14 => -13
+    [Z, N, N], // 15 => -12
+    [P, N, N], // 16 => -11
+    [N, Z, N], // 17 => -10
+    [Z, Z, N], // 18 => -9
+    [P, Z, N], // 19 => -8
+    [N, P, N], // 20 => -7
+    [Z, P, N], // 21 => -6
+    [P, P, N], // 22 => -5
+    [N, N, Z], // 23 => -4
+    [Z, N, Z], // 24 => -3
+    [P, N, Z], // 25 => -2
+    [N, Z, Z], // 26 => -1
 ];
 
 //  0 => 57
@@ -40,9 +40,12 @@ pub(crate) const TRYTE_CODE_TO_TRITS: [[Trit; 3]; 27] = [
 // ...
 // 26 => 90
 // LUT-SIZE: 27 Byte
+#[rustfmt::skip]
 pub(crate) const TRYTE_CODE_TO_ASCII_CODE: [Tryte; 27] = [
-    57, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87,
-    88, 89, 90,
+    Tryte(57), Tryte(65), Tryte(66), Tryte(67), Tryte(68), Tryte(69), Tryte(70), Tryte(71),
+    Tryte(72), Tryte(73), Tryte(74), Tryte(75), Tryte(76), Tryte(77), Tryte(78), Tryte(79),
+    Tryte(80), Tryte(81), Tryte(82), Tryte(83), Tryte(84), Tryte(85), Tryte(86), Tryte(87),
+    Tryte(88), Tryte(89), Tryte(90),
 ];
 
 //  0 => 57
@@ -50,56 +53,84 @@ pub(crate) const TRYTE_CODE_TO_ASCII_CODE: [Tryte; 27] = [
 // ...
 // 26 => 65
 // LUT-SIZE: 27 Byte
+#[rustfmt::skip]
 pub(crate) const TRYTE_CODE_TO_ASCII_CODE_NEG: [Tryte; 27] = [
-    57, 90, 89, 88, 87, 86, 85, 84, 83, 82, 81, 80, 79, 78, 77, 76, 75, 74, 73, 72, 71, 70, 69, 68,
-    67, 66, 65,
+    Tryte(57), Tryte(90), Tryte(89), Tryte(88), Tryte(87), Tryte(86), Tryte(85), Tryte(84),
+    Tryte(83), Tryte(82), Tryte(81), Tryte(80), Tryte(79), Tryte(78), Tryte(77), Tryte(76),
+    Tryte(75), Tryte(74), Tryte(73), Tryte(72), Tryte(71), Tryte(70), Tryte(69), Tryte(68),
+    Tryte(67), Tryte(66), Tryte(65),
 ];
 
+/// The ASCII code of the tryte `9`, by which the LUTs indexed by ASCII codes are offset.
+pub(crate) const ASCII_CODE_OFFSET: u8 = 57;
+
 // 57 => 0
+// 58..=64 => 0 (not a tryte)
 // 65 => 1
 // ...
 // 90 => 26
-// LUT-SIZE: 2 Byte * 27 = 54 Byte
-lazy_static! {
-    pub(crate) static ref ASCII_CODE_TO_TRYTE_CODE: FnvIndexMap::<Tryte, usize, U32> =
-        { HashMap::from_iter(TRYTE_CODE_TO_ASCII_CODE.iter().enumerate().map(|(v, &k)| (k, v))) };
-}
+// LUT-SIZE: 34 Byte
+pub(crate) const ASCII_CODE_TO_TRYTE_CODE: [u8; 34] = {
+    let mut lut = [0; 34];
+    let mut i = 0;
+    while i < 27 {
+        lut[(TRYTE_CODE_TO_ASCII_CODE[i].0 - ASCII_CODE_OFFSET) as usize] = i as u8;
+        i += 1;
+    }
+    lut
+};
+
+// 57 => [Z, Z, Z]
+// 58..=64 => [Z, Z, Z] (not a tryte)
+// 65 => [P, Z, Z]
+// ...
+// 90 => [N, Z, Z]
+// LUT-SIZE: 3 Byte * 34 = 102 Byte
+pub(crate) const ASCII_CODE_TO_TRITS: [[Trit; 3]; 34] = {
+    let mut lut = [[Z, Z, Z]; 34];
+    let mut i = 0;
+    while i < 27 {
+        lut[(TRYTE_CODE_TO_ASCII_CODE[i].0 - ASCII_CODE_OFFSET) as usize] = TRYTE_CODE_TO_TRITS[i];
+        i += 1;
+    }
+    lut
+};
+
+// 57 => 0
+// 58..=64 => 0 (not a tryte)
+// 65 => 1
+// ...
+// 77 => 13
+// 78 => -13
+// ...
+// 90 => -1
+// LUT-SIZE: 34 Byte
+pub(crate) const ASCII_CODE_TO_VALUE: [i8; 34] = {
+    let mut lut = [0; 34];
+    let mut i = 0;
+    while i < 27 {
+        let value = if i > 13 { i as i8 - 27 } else { i as i8 };
+        lut[(TRYTE_CODE_TO_ASCII_CODE[i].0 - ASCII_CODE_OFFSET) as usize] = value;
+        i += 1;
+    }
+    lut
+};
 
-// LUT-SIZE: 4 Byte * 32 = 128 Byte
-lazy_static! {
-    pub(crate) static ref ASCII_CODE_TO_TRITS: FnvIndexMap::<Tryte, [Trit; 3], U32> = [
-        (57, [0, 0, 0]),
-        (65, [1, 0, 0]),
-        (66, [-1, 1, 0]),
-        (67, [0, 1, 0]),
-        (68, [1, 1, 0]),
-        (69, [-1, -1, 1]),
-        (70, [0, -1, 1]),
-        (71, [1, -1, 1]),
-        (72, [-1, 0, 1]),
-        (73, [0, 0, 1]),
-        (74, [1, 0, 1]),
-        (75, [-1, 1, 1]),
-        (76, [0, 1, 1]),
-        (77, [1, 1, 1]),
-        (78, [-1, -1, -1]),
-        (79, [0, -1, -1]),
-        (80, [1, -1, -1]),
-        (81, [-1, 0, -1]),
-        (82, [0, 0, -1]),
-        (83, [1, 0, -1]),
-        (84, [-1, 1, -1]),
-        (85, [0, 1, -1]),
-        (86, [1, 1, -1]),
-        (87, [-1, -1, 0]),
-        (88, [0, -1, 0]),
-        (89, [1, -1, 0]),
-        (90, [-1, 0, 0])
-    ]
-    .iter()
-    .cloned()
-    .collect();
-}
+// -13 => 78
+// ...
+//   0 => 57
+// ...
+//  13 => 77
+// LUT-SIZE: 27 Byte
+pub(crate) const VALUE_TO_ASCII_CODE: [Tryte; 27] = {
+    let mut lut = [TRYTE_CODE_TO_ASCII_CODE[0]; 27];
+    let mut i = 0;
+    while i < 27 {
+        lut[i] = TRYTE_CODE_TO_ASCII_CODE[(i + 14) % 27];
+        i += 1;
+    }
+    lut
+};
 
 // TODO: Make using this LUT optional!
 // LUT-SIZE: 8 Byte * 34 * 11 = 2992 Byte
@@ -0,0 +1,69 @@
+//! Macros that convert tryte string literals at compile time.
+
+/// Converts a tryte string literal to an array of trytes at compile time.
+///
+/// Invalid trytes fail the build instead of panicking at runtime.
+///
+/// ```
+/// use trinary::{trytes, types::Tryte};
+///
+/// const TAG: [Tryte; 7] = trytes!("TRINITY");
+/// assert_eq!('T', TAG[0].as_char());
+/// ```
+///
+/// ```compile_fail
+/// let trytes = trinary::trytes!("TRiNITY");
+/// ```
+#[macro_export]
+macro_rules! trytes {
+    ($tryte_str:expr) => {{
+        const TRYTES: [$crate::types::Tryte; $tryte_str.len()] =
+            $crate::trytes::from_tryte_str_const($tryte_str);
+        TRYTES
+    }};
+}
+
+/// Converts a tryte string literal to an array of trits at compile time.
+///
+/// Invalid trytes fail the build instead of panicking at runtime.
+///
+/// ```
+/// use trinary::{trits, types::Trit};
+///
+/// const A9: [Trit; 6] = trits!("A9");
+/// assert_eq!(Trit::Pos, A9[0]);
+/// ```
+#[macro_export]
+macro_rules! trits {
+    ($tryte_str:expr) => {{
+        const TRITS: [$crate::types::Trit; $tryte_str.len() * 3] =
+            $crate::trits::from_tryte_str_const($tryte_str);
+        TRITS
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{Trit, Tryte};
+
+    const NULL_HASH: [Tryte; 81] = trytes!(
+        "999999999999999999999999999999999999999999999999999999999999999999999999999999999"
+    );
+
+    #[test]
+    fn test_trytes() {
+        assert_eq!(crate::trytes::from_tryte_str("ABC9"), trytes!("ABC9"));
+        assert!(NULL_HASH.iter().all(|&t| t == Tryte::default()));
+
+        const TAG: &str = "TRINITY99999999999999999999";
+        assert_eq!(crate::trytes::from_tryte_str(TAG), trytes!(TAG));
+    }
+
+    #[test]
+    fn test_trits() {
+        assert_eq!(crate::trits::from_tryte_str("ABC9"), trits!("ABC9"));
+
+        const SEG: [Trit; 9] = trits!("SEG");
+        assert_eq!(crate::trits::from_raw(&[1, 0, -1, -1, -1, 1, 1, -1, 1]), SEG);
+    }
+}
@@ -0,0 +1,122 @@
+//! Normalization of bundle hashes for Winternitz one-time signatures.
+//!
+//! The 81 trytes of a bundle hash are split into three chunks of 27 trytes, one per security
+//! level. Every tryte is interpreted as a value in `-13..=13`, and the values of each chunk are
+//! adjusted, starting at its first tryte, until they sum to zero.
+
+use crate::{
+    numbers::tryte_to_value,
+    numbers::MAX_TRYTE_VALUE_ABS,
+    types::Tryte,
+};
+
+/// The number of trytes of a bundle hash.
+pub const BUNDLE_HASH_LENGTH: usize = 81;
+
+/// The number of trytes of a chunk, which is signed by one key fragment.
+pub const CHUNK_LENGTH: usize = 27;
+
+/// Normalizes a bundle hash, so the values of each chunk of 27 trytes sum to zero.
+pub fn bundle_hash(hash: &[Tryte; BUNDLE_HASH_LENGTH]) -> [i8; BUNDLE_HASH_LENGTH] {
+    let mut normalized = [0_i8; BUNDLE_HASH_LENGTH];
+
+    for (chunk, trytes) in
+        normalized.chunks_exact_mut(CHUNK_LENGTH).zip(hash.chunks_exact(CHUNK_LENGTH))
+    {
+        let mut sum = 0_i16;
+        for (value, &tryte) in chunk.iter_mut().zip(trytes.iter()) {
+            *value = tryte_to_value(tryte);
+            sum += i16::from(*value);
+        }
+
+        // Moving every value as far as possible towards the bound, in order, gives the same
+        // result as the reference implementation that moves the first movable value by one.
+        for value in chunk.iter_mut() {
+            if sum > 0 {
+                let delta = sum.min(i16::from(*value + MAX_TRYTE_VALUE_ABS));
+                *value -= delta as i8;
+                sum -= delta;
+            } else if sum < 0 {
+                let delta = (-sum).min(i16::from(MAX_TRYTE_VALUE_ABS - *value));
+                *value += delta as i8;
+                sum += delta;
+            } else {
+                break;
+            }
+        }
+    }
+
+    normalized
+}
+
+/// Returns whether normalized values contain the insecure `M`, i.e. `13`.
+///
+/// Signing a hash fragment with the value `13` reveals the last key of the Winternitz chain, so a
+/// bundle hash whose normalized chunks contain it should be regenerated, usually by incrementing
+/// the obsolete tag. Only the chunks of the used security level have to be checked, so pass
+/// `&normalized[..security * 27]`.
+pub fn contains_insecure_m(normalized: &[i8]) -> bool {
+    normalized.contains(&MAX_TRYTE_VALUE_ABS)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::string::String;
+
+    use super::*;
+    use crate::{numbers, trytes};
+
+    fn normalize(hash: &str) -> String {
+        bundle_hash(&trytes::from_tryte_str_const(hash))
+            .iter()
+            .map(|&v| char::from(numbers::value_to_tryte(v)))
+            .collect()
+    }
+
+    #[test]
+    fn test_bundle_hash() {
+        let vectors = [
+            (
+                "KAQBMUGZK9BJGKCIYDARUZSLJEECJ9NXXGEWZYBFFLJOXBWZIANOKBOCYHYBISJPCEJFYGCNINBZRUJFB",
+                "NNNNOUGZK9BJGKCIYDARUZSLJEEOJ9NXXGEWZYBFFLJOXBWZIANOKBNOYHYBISJPCEJFYGCNINBZRUJFB",
+            ),
+            (
+                "QQSQLDHZ9999999999999999999TRINITY99999999999999999999TNXSQ9D99A99999999B99999999",
+                "EQSQLDHZ9999999999999999999MRINITY99999999999999999999MAXSQ9D99A99999999B99999999",
+            ),
+            (
+                "ZTDPEOIAPRD9WLJKPWHIMTUANYHKPZOOFPFUD9EMUVNKYTCVTCREFJJUCNOUNQX9Z9KSJUTFYFMTNUMYD",
+                "BTDPEOIAPRD9WLJKPWHIMTUANYHMKZOOFPFUD9EMUVNKYTCVTCREFJMMMUOUNQX9Z9KSJUTFYFMTNUMYD",
+            ),
+            (
+                "MMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMM",
+                "NNNNNNNNNNNNN9MMMMMMMMMMMMMNNNNNNNNNNNNN9MMMMMMMMMMMMMNNNNNNNNNNNNN9MMMMMMMMMMMMM",
+            ),
+            (
+                "NNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNNN",
+                "MMMMMMMMMMMMM9NNNNNNNNNNNNNMMMMMMMMMMMMM9NNNNNNNNNNNNNMMMMMMMMMMMMM9NNNNNNNNNNNNN",
+            ),
+        ];
+
+        for (hash, normalized) in vectors.iter() {
+            assert_eq!(*normalized, normalize(hash));
+        }
+    }
+
+    #[test]
+    fn test_contains_insecure_m() {
+        let hash = trytes::from_tryte_str_const::<81>(
+            "KAQBMUGZK9BJGKCIYDARUZSLJEECJ9NXXGEWZYBFFLJOXBWZIANOKBOCYHYBISJPCEJFYGCNINBZRUJFB",
+        );
+        assert!(!contains_insecure_m(&bundle_hash(&hash)));
+
+        let hash = trytes::from_tryte_str_const::<81>(
+            "QQSQLDHZ9999999999999999999TRINITY99999999999999999999TNXSQ9D99A99999999B99999999",
+        );
+        let normalized = bundle_hash(&hash);
+        assert!(contains_insecure_m(&normalized));
+        // The `M` is in the second chunk.
+        assert!(!contains_insecure_m(&normalized[..CHUNK_LENGTH]));
+    }
+}
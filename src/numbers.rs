@@ -1,18 +1,34 @@
 //! Converter functions that convert trits/trytes to numbers.
 //!
-//! NOTE: In the future it might be necessary to use `num-bigint`.
+//! Numbers that don't fit into an `S129`, like 243 trit hashes, can be handled by
+//! `big_ternary::BigTernary`.
 //!
 //! Currently 11 trytes are enough to represent the IOTA supply
 //! of (3^(3*11)-1)/2 = 2,779530283×10^15
 //! (2^64-1)/2 = 9,223372037×10^18
+//!
+//! The balanced value of a single tryte, `-13..=13`, is converted by `tryte_to_value` and
+//! `value_to_tryte`.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 
 use crate::{
+    constants::TRYTE_LENGTH_FOR_MAX_I64,
+    constants::TRYTE_LENGTH_FOR_MAX_TOKEN_SUPPLY,
+    error::ConversionError,
+    error::ErrorKind,
+    luts::ASCII_CODE_OFFSET,
     luts::ASCII_CODE_SEQ_TO_NUM,
-    luts::ASCII_CODE_TO_TRITS,
+    luts::ASCII_CODE_TO_VALUE,
+    luts::VALUE_TO_ASCII_CODE,
+    trits::from_tryte,
     types::Sign,
     types::Trit,
     types::Tryte,
     types::S129,
+    util::check_buf_len,
+    util::check_max_len,
 };
 
 /// Converts up to 11 trytes to an `i64` by using only a LUT and addition.
@@ -21,13 +37,24 @@ use crate::{
 pub fn from_trytes_max11(trytes: &[Tryte]) -> i64 {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert!(crate::util::is_trytes(&trytes));
-        assert!(trytes.len() <= crate::constants::TRYTE_LENGTH_FOR_MAX_TOKEN_SUPPLY);
+        check_max_len(trytes.len(), TRYTE_LENGTH_FOR_MAX_TOKEN_SUPPLY).unwrap();
     }
 
+    max11_inner(trytes)
+}
+
+/// Tries to convert up to 11 trytes to an `i64`.
+pub fn try_from_trytes_max11(trytes: &[Tryte]) -> Result<i64, ConversionError> {
+    check_max_len(trytes.len(), TRYTE_LENGTH_FOR_MAX_TOKEN_SUPPLY)?;
+
+    Ok(max11_inner(trytes))
+}
+
+#[inline]
+fn max11_inner(trytes: &[Tryte]) -> i64 {
     let mut number = 0;
     trytes.iter().enumerate().for_each(|(i, &t)| {
-        number += ASCII_CODE_SEQ_TO_NUM[i][t as usize - 57];
+        number += ASCII_CODE_SEQ_TO_NUM[i][t.0 as usize - 57];
     });
 
     number
@@ -39,15 +66,26 @@ pub fn from_trytes_max11(trytes: &[Tryte]) -> i64 {
 pub fn from_trytes_max13(trytes: &[Tryte]) -> i64 {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert!(crate::util::is_trytes(&trytes));
-        assert!(trytes.len() <= crate::constants::TRYTE_LENGTH_FOR_MAX_I64);
+        check_max_len(trytes.len(), TRYTE_LENGTH_FOR_MAX_I64).unwrap();
     }
 
+    max13_inner(trytes)
+}
+
+/// Tries to convert up to 13 trytes to an `i64`.
+pub fn try_from_trytes_max13(trytes: &[Tryte]) -> Result<i64, ConversionError> {
+    check_max_len(trytes.len(), TRYTE_LENGTH_FOR_MAX_I64)?;
+
+    Ok(max13_inner(trytes))
+}
+
+#[inline]
+fn max13_inner(trytes: &[Tryte]) -> i64 {
     let mut number = 0;
     trytes.iter().rev().for_each(|tryte| {
-        let trits = ASCII_CODE_TO_TRITS[tryte];
+        let trits = from_tryte(*tryte);
         for n in (0..trits.len()).rev() {
-            number = number * 3 + i64::from(trits[n]);
+            number = number * 3 + trits[n] as i64;
         }
     });
 
@@ -60,36 +98,47 @@ pub fn from_trytes_max13(trytes: &[Tryte]) -> i64 {
 pub fn from_trytes_max27(trytes: &[Tryte]) -> S129 {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert!(crate::util::is_trytes(&trytes));
-        assert!(trytes.len() <= 27);
+        check_max_len(trytes.len(), 27).unwrap();
     }
 
+    max27_inner(trytes)
+}
+
+/// Tries to convert up to 27 trytes to an `S129`.
+pub fn try_from_trytes_max27(trytes: &[Tryte]) -> Result<S129, ConversionError> {
+    check_max_len(trytes.len(), 27)?;
+
+    Ok(max27_inner(trytes))
+}
+
+#[inline]
+fn max27_inner(trytes: &[Tryte]) -> S129 {
     let mut sign = None;
     let mut number = 0_u128;
 
     trytes.iter().rev().for_each(|tryte| {
-        let trits = ASCII_CODE_TO_TRITS[tryte];
+        let trits = from_tryte(*tryte);
         for n in (0..3).rev() {
             if sign.is_none() {
                 sign = match trits[n] {
-                    -1 => Some(Sign::Neg),
-                    1 => Some(Sign::Pos),
-                    _ => None,
+                    Trit::Neg => Some(Sign::Neg),
+                    Trit::Pos => Some(Sign::Pos),
+                    Trit::Zero => None,
                 }
             }
             number *= 3;
             match trits[n] {
-                -1 => match sign {
+                Trit::Neg => match sign {
                     Some(Sign::Pos) => number -= 1,
                     Some(Sign::Neg) => number += 1,
                     _ => (),
                 },
-                1 => match sign {
+                Trit::Pos => match sign {
                     Some(Sign::Pos) => number += 1,
                     Some(Sign::Neg) => number -= 1,
                     _ => (),
                 },
-                _ => (),
+                Trit::Zero => (),
             }
         }
     });
@@ -101,64 +150,168 @@ pub fn from_trytes_max27(trytes: &[Tryte]) -> S129 {
 pub fn from_trits(trits: &[Trit]) -> i64 {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert!(crate::util::is_trits(&trits));
-        assert!(trits.len() <= 20);
+        check_max_len(trits.len(), 20).unwrap();
     }
 
+    trits_inner(trits)
+}
+
+/// Tries to convert a slice of up to 20 trits to an `i64`.
+pub fn try_from_trits(trits: &[Trit]) -> Result<i64, ConversionError> {
+    check_max_len(trits.len(), 20)?;
+
+    Ok(trits_inner(trits))
+}
+
+#[inline]
+fn trits_inner(trits: &[Trit]) -> i64 {
     let mut number = 0;
 
     for n in (0..trits.len()).rev() {
-        number = number * 3 + i64::from(trits[n]);
+        number = number * 3 + trits[n] as i64;
     }
 
     number
 }
 
+/// The largest absolute value of a single tryte.
+pub(crate) const MAX_TRYTE_VALUE_ABS: i8 = 13;
+
+/// Returns the balanced value of a tryte, `-13..=13`.
+pub const fn tryte_to_value(tryte: Tryte) -> i8 {
+    ASCII_CODE_TO_VALUE[(tryte.0 - ASCII_CODE_OFFSET) as usize]
+}
+
+/// Converts a balanced value to a tryte.
+///
+/// This function will panic if the value is not in `-13..=13`.
+pub fn value_to_tryte(value: i8) -> Tryte {
+    try_value_to_tryte(value).unwrap()
+}
+
+/// Tries to convert a balanced value to a tryte.
+///
+/// Fails if the value is not in `-13..=13`.
+pub fn try_value_to_tryte(value: i8) -> Result<Tryte, ConversionError> {
+    check_values(&[value])?;
+
+    Ok(value_to_tryte_inner(value))
+}
+
+/// Converts trytes to their balanced values.
+#[cfg(feature = "alloc")]
+pub fn trytes_to_values(trytes: &[Tryte]) -> Vec<i8> {
+    trytes.iter().map(|&t| tryte_to_value(t)).collect()
+}
+
+/// Converts trytes to their balanced values, writing them into `values`.
+///
+/// Returns the number of values written.
+pub fn trytes_to_values_into(
+    trytes: &[Tryte],
+    values: &mut [i8],
+) -> Result<usize, ConversionError> {
+    check_buf_len(values.len(), trytes.len())?;
+
+    values.iter_mut().zip(trytes.iter()).for_each(|(v, &t)| *v = tryte_to_value(t));
+    Ok(trytes.len())
+}
+
+/// Converts balanced values to trytes.
+///
+/// This function will panic if a value is not in `-13..=13`.
+#[cfg(feature = "alloc")]
+pub fn values_to_trytes(values: &[i8]) -> Vec<Tryte> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_values(values).unwrap();
+    }
+
+    values.iter().map(|&v| value_to_tryte_inner(v)).collect()
+}
+
+/// Tries to convert balanced values to trytes.
+///
+/// Fails if a value is not in `-13..=13`.
+#[cfg(feature = "alloc")]
+pub fn try_values_to_trytes(values: &[i8]) -> Result<Vec<Tryte>, ConversionError> {
+    check_values(values)?;
+
+    Ok(values.iter().map(|&v| value_to_tryte_inner(v)).collect())
+}
+
+/// Converts balanced values to trytes, writing them into `trytes`.
+///
+/// Returns the number of trytes written.
+pub fn values_to_trytes_into(
+    values: &[i8],
+    trytes: &mut [Tryte],
+) -> Result<usize, ConversionError> {
+    check_values(values)?;
+    check_buf_len(trytes.len(), values.len())?;
+
+    trytes.iter_mut().zip(values.iter()).for_each(|(t, &v)| *t = value_to_tryte_inner(v));
+    Ok(values.len())
+}
+
+#[inline]
+fn value_to_tryte_inner(value: i8) -> Tryte {
+    VALUE_TO_ASCII_CODE[(value + MAX_TRYTE_VALUE_ABS) as usize]
+}
+
+fn check_values(values: &[i8]) -> Result<(), ConversionError> {
+    match values.iter().position(|v| !(-MAX_TRYTE_VALUE_ABS..=MAX_TRYTE_VALUE_ABS).contains(v)) {
+        Some(index) => Err(ConversionError::new(ErrorKind::OutOfRange, index)),
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::trytes;
     use super::*;
+    use crate::error::ErrorKind;
     use rand::*;
 
     #[test]
     fn test_from_trytes_max11() {
-        let number = from_trytes_max11(&['9' as u8]);
+        let number = from_trytes_max11(&trytes::from_tryte_str("9"));
         assert_eq!(0, number);
 
-        let number = from_trytes_max11(&['A' as u8]);
+        let number = from_trytes_max11(&trytes::from_tryte_str("A"));
         assert_eq!(1, number);
 
-        let number = from_trytes_max11(&['M' as u8]);
+        let number = from_trytes_max11(&trytes::from_tryte_str("M"));
         assert_eq!(13, number);
 
-        let number = from_trytes_max11(&['A' as u8, '9' as u8]);
+        let number = from_trytes_max11(&trytes::from_tryte_str("A9"));
         assert_eq!(1, number);
 
-        let number = from_trytes_max11(&['9' as u8, 'A' as u8]);
+        let number = from_trytes_max11(&trytes::from_tryte_str("9A"));
         assert_eq!(27, number);
 
-        let number = from_trytes_max11(&['N' as u8]);
+        let number = from_trytes_max11(&trytes::from_tryte_str("N"));
         assert_eq!(-13, number);
     }
 
     #[test]
     fn test_from_trytes_max13() {
-        let number = from_trytes_max13(&['9' as u8]);
+        let number = from_trytes_max13(&trytes::from_tryte_str("9"));
         assert_eq!(0, number);
 
-        let number = from_trytes_max13(&['A' as u8]);
+        let number = from_trytes_max13(&trytes::from_tryte_str("A"));
         assert_eq!(1, number);
 
-        let number = from_trytes_max13(&['M' as u8]);
+        let number = from_trytes_max13(&trytes::from_tryte_str("M"));
         assert_eq!(13, number);
 
-        let number = from_trytes_max13(&['A' as u8, '9' as u8]);
+        let number = from_trytes_max13(&trytes::from_tryte_str("A9"));
         assert_eq!(1, number);
 
-        let number = from_trytes_max13(&['9' as u8, 'A' as u8]);
+        let number = from_trytes_max13(&trytes::from_tryte_str("9A"));
         assert_eq!(27, number);
 
-        let number = from_trytes_max11(&['N' as u8]);
+        let number = from_trytes_max11(&trytes::from_tryte_str("N"));
         assert_eq!(-13, number);
     }
 
@@ -174,47 +327,113 @@ mod tests {
 
     #[test]
     fn test_from_trytes_max27() {
-        let number = from_trytes_max27("9".as_bytes());
+        let number = from_trytes_max27(&trytes::from_tryte_str("9"));
         assert_eq!(S129(Sign::Pos, 0), number);
 
-        let number = from_trytes_max27("A".as_bytes());
+        let number = from_trytes_max27(&trytes::from_tryte_str("A"));
         assert_eq!(S129(Sign::Pos, 1), number);
 
-        let number = from_trytes_max27("M".as_bytes());
+        let number = from_trytes_max27(&trytes::from_tryte_str("M"));
         assert_eq!(S129(Sign::Pos, 13), number);
 
-        let number = from_trytes_max27("A9".as_bytes());
+        let number = from_trytes_max27(&trytes::from_tryte_str("A9"));
         assert_eq!(S129(Sign::Pos, 1), number);
 
-        let number = from_trytes_max27("9A".as_bytes());
+        let number = from_trytes_max27(&trytes::from_tryte_str("9A"));
         assert_eq!(S129(Sign::Pos, 27), number);
 
-        let number = from_trytes_max27("N".as_bytes());
+        let number = from_trytes_max27(&trytes::from_tryte_str("N"));
         assert_eq!(S129(Sign::Neg, 13), number);
 
-        let number = from_trytes_max27("NN".as_bytes());
+        let number = from_trytes_max27(&trytes::from_tryte_str("NN"));
         assert_eq!(S129(Sign::Neg, 364), number);
 
-        let number = from_trytes_max27("MMMMMMMMMMMMMMMMMMMMMMMMMMM".as_bytes());
+        let number = from_trytes_max27(&trytes::from_tryte_str("MMMMMMMMMMMMMMMMMMMMMMMMMMM"));
         assert_eq!(S129(Sign::Pos, 221713244121518884974124815309574946401), number);
 
-        let number = from_trytes_max27("NNNNNNNNNNNNNNNNNNNNNNNNNNN".as_bytes());
+        let number = from_trytes_max27(&trytes::from_tryte_str("NNNNNNNNNNNNNNNNNNNNNNNNNNN"));
         assert_eq!(S129(Sign::Neg, 221713244121518884974124815309574946401), number);
     }
 
+    #[test]
+    fn test_try_from_trytes() {
+        assert_eq!(Ok(27), try_from_trytes_max11(&trytes::from_tryte_str("9A")));
+        assert_eq!(Ok(27), try_from_trytes_max13(&trytes::from_tryte_str("9A")));
+        assert_eq!(Ok(S129(Sign::Pos, 27)), try_from_trytes_max27(&trytes::from_tryte_str("9A")));
+
+        let err = try_from_trytes_max11(&trytes::from_tryte_str("999999999999")).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(12, err.index());
+
+        let err = try_from_trytes_max13(&trytes::from_tryte_str("99999999999999")).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+    }
+
+    #[test]
+    fn test_try_from_trits() {
+        assert_eq!(Ok(14), try_from_trits(&crate::trits::from_raw(&[-1, -1, -1, 1])));
+        assert_eq!(ErrorKind::InvalidLength, try_from_trits(&[Trit::Zero; 21]).unwrap_err().kind());
+    }
+
     #[test]
     fn test_from_trits() {
-        let number = from_trits(&[0, 0, 0]);
+        let number = from_trits(&crate::trits::from_raw(&[0, 0, 0]));
         assert_eq!(0, number);
 
-        let number = from_trits(&[1, 1, 1]);
+        let number = from_trits(&crate::trits::from_raw(&[1, 1, 1]));
         assert_eq!(13, number);
 
-        let number = from_trits(&[-1, -1, -1]);
+        let number = from_trits(&crate::trits::from_raw(&[-1, -1, -1]));
         assert_eq!(-13, number);
 
-        let number = from_trits(&[-1, -1, -1, 1]);
+        let number = from_trits(&crate::trits::from_raw(&[-1, -1, -1, 1]));
         assert_eq!(14, number);
     }
 
+    #[test]
+    fn test_tryte_to_value() {
+        let values: Vec<i8> =
+            trytes::from_tryte_str("9AMNZ").iter().map(|&t| tryte_to_value(t)).collect();
+        assert_eq!(vec![0, 1, 13, -13, -1], values);
+
+        for tryte in trytes::from_tryte_str("9ABCDEFGHIJKLMNOPQRSTUVWXYZ") {
+            assert_eq!(from_trytes_max11(&[tryte]), i64::from(tryte_to_value(tryte)));
+        }
+    }
+
+    #[test]
+    fn test_value_to_tryte() {
+        for value in -13..=13 {
+            assert_eq!(value, tryte_to_value(value_to_tryte(value)));
+        }
+        assert_eq!(trytes::from_tryte_str("N"), vec![value_to_tryte(-13)]);
+
+        assert_eq!(ErrorKind::OutOfRange, try_value_to_tryte(14).unwrap_err().kind());
+        assert_eq!(ErrorKind::OutOfRange, try_value_to_tryte(i8::MIN).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_slices() {
+        let trytes = trytes::from_tryte_str("HELLOWORLD9MN");
+        let values = trytes_to_values(&trytes);
+        assert_eq!(vec![8, 5, 12, 12, -12, -4, -12, -9, 12, 4, 0, 13, -13], values);
+        assert_eq!(trytes, values_to_trytes(&values));
+
+        let mut buf = [0_i8; 13];
+        assert_eq!(Ok(13), trytes_to_values_into(&trytes, &mut buf));
+        assert_eq!(&values[..], &buf[..]);
+        assert_eq!(
+            ErrorKind::BufferTooSmall,
+            trytes_to_values_into(&trytes, &mut buf[..12]).unwrap_err().kind()
+        );
+
+        let mut buf = [Tryte::default(); 13];
+        assert_eq!(Ok(13), values_to_trytes_into(&values, &mut buf));
+        assert_eq!(&trytes[..], &buf[..]);
+
+        let err = try_values_to_trytes(&[0, 13, -14, 20]).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(2, err.index());
+        assert_eq!(2, values_to_trytes_into(&[0, 13, -14], &mut buf).unwrap_err().index());
+    }
 }
@@ -0,0 +1,640 @@
+//! Vectorized conversions between trytes, trits and bytes in the 9/2 encoding.
+//!
+//! On `x86_64` the conversions use AVX2 if the CPU supports it, which is detected at runtime with
+//! `std` and at compile time without it, and SSE2 otherwise, which every `x86_64` CPU supports.
+//! Other targets use the scalar converters. The kernels convert the input in blocks and leave the
+//! remainder to the scalar converters, so the output is the same in every case.
+//!
+//! Groups of 3 trits or trytes are moved between packed and vector form by spreading 12 bytes
+//! over the low 3 bytes of the 4 lanes of 32 bits (`expand`), and the reverse (`compact`). The
+//! stores of `compact`ed lanes write 4 bytes past the 12 valid ones, which are overwritten by the
+//! next store, so the kernels stop early enough for them to stay within the output.
+
+use crate::{bytes, trits, trytes, types::Byte, types::Trit, types::Tryte};
+
+/// Converts trytes to trits, `trits` has to hold 3 trits per tryte.
+#[inline]
+pub(crate) fn trytes_to_trits(trytes: &[Tryte], trits: &mut [Trit]) {
+    debug_assert_eq!(trytes.len() * 3, trits.len());
+
+    #[cfg(target_arch = "x86_64")]
+    let done = x86::trytes_to_trits(trytes, trits);
+    #[cfg(not(target_arch = "x86_64"))]
+    let done = 0;
+
+    trits::write_from_trytes(&trytes[done..], &mut trits[done * 3..]);
+}
+
+/// Converts trits to bytes, `bytes` has to hold 2 bytes per 9 trits.
+#[inline]
+pub(crate) fn trits_to_bytes(trits: &[Trit], bytes: &mut [Byte]) {
+    debug_assert_eq!(trits.len() / 9 * 2, bytes.len());
+
+    #[cfg(target_arch = "x86_64")]
+    let done = x86::trits_to_bytes(trits, bytes);
+    #[cfg(not(target_arch = "x86_64"))]
+    let done = 0;
+
+    bytes::write_from_trits(&trits[done / 2 * 9..], &mut bytes[done..]);
+}
+
+/// Converts valid bytes to trytes, `trytes` has to hold 3 trytes per 2 bytes.
+#[inline]
+pub(crate) fn bytes_to_trytes(bytes: &[Byte], trytes: &mut [Tryte]) {
+    debug_assert_eq!(bytes.len() / 2 * 3, trytes.len());
+
+    #[cfg(target_arch = "x86_64")]
+    let done = x86::bytes_to_trytes(bytes, trytes);
+    #[cfg(not(target_arch = "x86_64"))]
+    let done = 0;
+
+    trytes::write_from_bytes(&bytes[done..], &mut trytes[done / 2 * 3..]);
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use core::arch::x86_64::*;
+
+    use crate::types::{Byte, Trit, Tryte};
+
+    const ASCII_9: i8 = b'9' as i8;
+    const ASCII_A_MINUS_1: i8 = b'A' as i8 - 1;
+
+    #[inline]
+    pub(super) fn has_avx2() -> bool {
+        #[cfg(feature = "std")]
+        {
+            std::is_x86_feature_detected!("avx2")
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            cfg!(target_feature = "avx2")
+        }
+    }
+
+    /// Converts a prefix of the trytes, returning the number of converted trytes.
+    #[inline]
+    pub(super) fn trytes_to_trits(trytes: &[Tryte], trits: &mut [Trit]) -> usize {
+        // SAFETY: the kernels only need SSE2, which is part of `x86_64`, or the detected AVX2.
+        unsafe {
+            if has_avx2() {
+                trytes_to_trits_avx2(trytes, trits)
+            } else {
+                trytes_to_trits_sse2(trytes, trits)
+            }
+        }
+    }
+
+    /// Converts a prefix of the trits, returning the number of written bytes.
+    #[inline]
+    pub(super) fn trits_to_bytes(trits: &[Trit], bytes: &mut [Byte]) -> usize {
+        // SAFETY: see `trytes_to_trits`.
+        unsafe {
+            if has_avx2() {
+                trits_to_bytes_avx2(trits, bytes)
+            } else {
+                trits_to_bytes_sse2(trits, bytes)
+            }
+        }
+    }
+
+    /// Converts a prefix of the bytes, returning the number of converted bytes.
+    #[inline]
+    pub(super) fn bytes_to_trytes(bytes: &[Byte], trytes: &mut [Tryte]) -> usize {
+        // SAFETY: see `trytes_to_trits`.
+        unsafe {
+            if has_avx2() {
+                bytes_to_trytes_avx2(bytes, trytes)
+            } else {
+                bytes_to_trytes_sse2(bytes, trytes)
+            }
+        }
+    }
+
+    /// Converts 16 trytes per iteration.
+    pub(super) unsafe fn trytes_to_trits_sse2(trytes: &[Tryte], trits: &mut [Trit]) -> usize {
+        let src = trytes.as_ptr().cast::<u8>();
+        let dst = trits.as_mut_ptr().cast::<u8>();
+        let zero = _mm_setzero_si128();
+
+        let mut i = 0;
+        while i + 16 <= trytes.len() && 3 * i + 52 <= trits.len() {
+            let [t0, t1, t2] = trits_of_sse2(_mm_loadu_si128(src.add(i).cast()));
+            let t01_lo = _mm_unpacklo_epi8(t0, t1);
+            let t01_hi = _mm_unpackhi_epi8(t0, t1);
+            let t2_lo = _mm_unpacklo_epi8(t2, zero);
+            let t2_hi = _mm_unpackhi_epi8(t2, zero);
+            let groups = [
+                _mm_unpacklo_epi16(t01_lo, t2_lo),
+                _mm_unpackhi_epi16(t01_lo, t2_lo),
+                _mm_unpacklo_epi16(t01_hi, t2_hi),
+                _mm_unpackhi_epi16(t01_hi, t2_hi),
+            ];
+            for (k, group) in groups.iter().enumerate() {
+                _mm_storeu_si128(dst.add(3 * i + 12 * k).cast(), compact_sse2(*group));
+            }
+            i += 16;
+        }
+        i
+    }
+
+    /// Converts 48 trytes of trits to 32 bytes per iteration.
+    pub(super) unsafe fn trits_to_bytes_sse2(trits: &[Trit], bytes: &mut [Byte]) -> usize {
+        let src = trits.as_ptr().cast::<u8>();
+        let dst = bytes.as_mut_ptr();
+        let mut codes = [0_u8; 64];
+
+        let mut i = 0;
+        while 9 * i / 2 + 148 <= trits.len() && i + 32 <= bytes.len() {
+            for k in 0..3 {
+                let codes_16 = tryte_codes_sse2(src.add(9 * i / 2 + 48 * k));
+                _mm_storeu_si128(codes.as_mut_ptr().add(16 * k).cast(), codes_16);
+            }
+            let mut pairs = [_mm_setzero_si128(); 4];
+            for (k, pair) in pairs.iter_mut().enumerate() {
+                *pair = bytes_of_codes_sse2(expand_sse2(_mm_loadu_si128(
+                    codes.as_ptr().add(12 * k).cast(),
+                )));
+            }
+            _mm_storeu_si128(dst.add(i).cast(), _mm_packs_epi32(pairs[0], pairs[1]));
+            _mm_storeu_si128(dst.add(i + 16).cast(), _mm_packs_epi32(pairs[2], pairs[3]));
+            i += 32;
+        }
+        i
+    }
+
+    /// Converts 16 bytes per iteration.
+    pub(super) unsafe fn bytes_to_trytes_sse2(bytes: &[Byte], trytes: &mut [Tryte]) -> usize {
+        let src = bytes.as_ptr();
+        let dst = trytes.as_mut_ptr().cast::<u8>();
+
+        let mut i = 0;
+        while i + 16 <= bytes.len() && 3 * i / 2 + 28 <= trytes.len() {
+            let pairs = _mm_loadu_si128(src.add(i).cast());
+            let c0 = _mm_and_si128(_mm_srli_epi16(pairs, 3), _mm_set1_epi16(0x1F));
+            let c1 = _mm_srli_epi16(pairs, 11);
+            let c2 = _mm_or_si128(
+                _mm_and_si128(pairs, _mm_set1_epi16(0x07)),
+                _mm_and_si128(_mm_srli_epi16(pairs, 5), _mm_set1_epi16(0x38)),
+            );
+            let c01 = _mm_or_si128(c0, _mm_slli_epi16(c1, 8));
+            let groups = [_mm_unpacklo_epi16(c01, c2), _mm_unpackhi_epi16(c01, c2)];
+            for (k, group) in groups.iter().enumerate() {
+                let trytes_12 = ascii_of_codes_sse2(compact_sse2(*group));
+                _mm_storeu_si128(dst.add(3 * i / 2 + 12 * k).cast(), trytes_12);
+            }
+            i += 16;
+        }
+        i
+    }
+
+    /// Returns the balanced trits `[t0, t1, t2]` of 16 trytes.
+    #[inline]
+    unsafe fn trits_of_sse2(trytes: __m128i) -> [__m128i; 3] {
+        // `A..=M` have the values 1..=13, `N..=Z` -13..=-1 and `9` 0.
+        let code = _mm_sub_epi8(trytes, _mm_set1_epi8(ASCII_A_MINUS_1));
+        let high = _mm_cmpgt_epi8(code, _mm_set1_epi8(13));
+        let value = _mm_sub_epi8(code, _mm_and_si128(high, _mm_set1_epi8(27)));
+        let value = _mm_andnot_si128(_mm_cmpeq_epi8(trytes, _mm_set1_epi8(ASCII_9)), value);
+
+        // The unbalanced digits of `value + 13` are the trits plus one. Splitting off every digit,
+        // instead of taking the last one as is, keeps the trits valid even for invalid trytes.
+        let rest = _mm_add_epi8(value, _mm_set1_epi8(13));
+        let (t2, rest) = split_digit_sse2(rest, 9);
+        let (t1, rest) = split_digit_sse2(rest, 3);
+        let (t0, _) = split_digit_sse2(rest, 1);
+        [t0, t1, t2]
+    }
+
+    /// Splits the digit of `weight` off `rest` in `0..3 * weight`, returning it as balanced trit.
+    ///
+    /// The trit is `-1`, `0` or `1` for any `rest`.
+    #[inline]
+    unsafe fn split_digit_sse2(rest: __m128i, weight: i8) -> (__m128i, __m128i) {
+        let ge1 = _mm_cmpgt_epi8(rest, _mm_set1_epi8(weight - 1));
+        let ge2 = _mm_cmpgt_epi8(rest, _mm_set1_epi8(2 * weight - 1));
+        let trit = _mm_sub_epi8(_mm_sub_epi8(_mm_set1_epi8(-1), ge1), ge2);
+        let weight = _mm_set1_epi8(weight);
+        let rest = _mm_sub_epi8(
+            _mm_sub_epi8(rest, _mm_and_si128(ge1, weight)),
+            _mm_and_si128(ge2, weight),
+        );
+        (trit, rest)
+    }
+
+    /// Returns the tryte codes of the 16 trytes of the 48 trits at `src`, reading 52 trits.
+    #[inline]
+    unsafe fn tryte_codes_sse2(src: *const u8) -> __m128i {
+        let mut codes = [_mm_setzero_si128(); 4];
+        for (k, code) in codes.iter_mut().enumerate() {
+            *code = codes_of_trits_sse2(expand_sse2(_mm_loadu_si128(src.add(12 * k).cast())));
+        }
+        _mm_packus_epi16(_mm_packs_epi32(codes[0], codes[1]), _mm_packs_epi32(codes[2], codes[3]))
+    }
+
+    /// Returns the tryte codes of the trits `[t0, t1, t2, 0]` in every lane.
+    #[inline]
+    unsafe fn codes_of_trits_sse2(trits: __m128i) -> __m128i {
+        let t1 = _mm_srli_epi32(trits, 8);
+        let t2 = _mm_srli_epi32(trits, 16);
+        let t1_3 = _mm_add_epi8(_mm_add_epi8(t1, t1), t1);
+        let t2_3 = _mm_add_epi8(_mm_add_epi8(t2, t2), t2);
+        let t2_9 = _mm_add_epi8(_mm_add_epi8(t2_3, t2_3), t2_3);
+        let value = _mm_add_epi8(_mm_add_epi8(trits, t1_3), t2_9);
+        let negative = _mm_cmpgt_epi8(_mm_setzero_si128(), value);
+        let code = _mm_add_epi8(value, _mm_and_si128(negative, _mm_set1_epi8(27)));
+        _mm_and_si128(code, _mm_set1_epi32(0xFF))
+    }
+
+    /// Returns the 2 bytes of the tryte codes `[c0, c1, c2, 0]` in every lane, sign extended.
+    #[inline]
+    unsafe fn bytes_of_codes_sse2(codes: __m128i) -> __m128i {
+        let c2 = _mm_srli_epi32(codes, 16);
+        let c01 = _mm_slli_epi16(_mm_and_si128(codes, _mm_set1_epi32(0xFFFF)), 3);
+        let c2 = _mm_or_si128(
+            _mm_and_si128(c2, _mm_set1_epi32(0x07)),
+            _mm_slli_epi32(_mm_srli_epi32(c2, 3), 8),
+        );
+        // Sign extend, so packing the lanes to 16 bits doesn't saturate.
+        _mm_srai_epi32(_mm_slli_epi32(_mm_or_si128(c01, c2), 16), 16)
+    }
+
+    /// Maps tryte codes to ASCII trytes.
+    #[inline]
+    unsafe fn ascii_of_codes_sse2(codes: __m128i) -> __m128i {
+        let nines = _mm_cmpeq_epi8(codes, _mm_setzero_si128());
+        _mm_sub_epi8(
+            _mm_add_epi8(codes, _mm_set1_epi8(ASCII_A_MINUS_1)),
+            _mm_and_si128(nines, _mm_set1_epi8(ASCII_A_MINUS_1 - ASCII_9)),
+        )
+    }
+
+    /// Spreads the low 12 bytes over the low 3 bytes of the 4 lanes.
+    #[inline]
+    unsafe fn expand_sse2(bytes: __m128i) -> __m128i {
+        let halves = _mm_unpacklo_epi64(bytes, _mm_srli_si128(bytes, 6));
+        let even = _mm_and_si128(halves, _mm_set1_epi64x(0xFF_FFFF));
+        let odd = _mm_and_si128(_mm_slli_epi64(halves, 8), _mm_set1_epi64x(0xFF_FFFF_0000_0000));
+        _mm_or_si128(even, odd)
+    }
+
+    /// Packs the low 3 bytes of the 4 lanes into the low 12 bytes, zeroing the others.
+    #[inline]
+    unsafe fn compact_sse2(lanes: __m128i) -> __m128i {
+        let even = _mm_and_si128(lanes, _mm_set1_epi64x(0xFF_FFFF));
+        let odd = _mm_and_si128(_mm_srli_epi64(lanes, 8), _mm_set1_epi64x(0xFFFF_FF00_0000));
+        let halves = _mm_or_si128(even, odd);
+        _mm_or_si128(_mm_move_epi64(halves), _mm_slli_si128(_mm_srli_si128(halves, 8), 6))
+    }
+
+    /// Converts 32 trytes per iteration.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn trytes_to_trits_avx2(trytes: &[Tryte], trits: &mut [Trit]) -> usize {
+        let src = trytes.as_ptr().cast::<u8>();
+        let dst = trits.as_mut_ptr().cast::<u8>();
+        let zero = _mm256_setzero_si256();
+
+        let mut i = 0;
+        while i + 32 <= trytes.len() && 3 * i + 100 <= trits.len() {
+            // The unpacks work within the 128 bit halves, the low one holds the first 16 trytes.
+            let [t0, t1, t2] = trits_of_avx2(_mm256_loadu_si256(src.add(i).cast()));
+            let t01_lo = _mm256_unpacklo_epi8(t0, t1);
+            let t01_hi = _mm256_unpackhi_epi8(t0, t1);
+            let t2_lo = _mm256_unpacklo_epi8(t2, zero);
+            let t2_hi = _mm256_unpackhi_epi8(t2, zero);
+            let groups = [
+                compact_avx2(_mm256_unpacklo_epi16(t01_lo, t2_lo)),
+                compact_avx2(_mm256_unpackhi_epi16(t01_lo, t2_lo)),
+                compact_avx2(_mm256_unpacklo_epi16(t01_hi, t2_hi)),
+                compact_avx2(_mm256_unpackhi_epi16(t01_hi, t2_hi)),
+            ];
+            for (k, group) in groups.iter().enumerate() {
+                _mm_storeu_si128(dst.add(3 * i + 12 * k).cast(), _mm256_castsi256_si128(*group));
+            }
+            for (k, group) in groups.iter().enumerate() {
+                let high = _mm256_extracti128_si256(*group, 1);
+                _mm_storeu_si128(dst.add(3 * i + 48 + 12 * k).cast(), high);
+            }
+            i += 32;
+        }
+        i
+    }
+
+    /// Converts 96 trytes of trits to 64 bytes per iteration.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn trits_to_bytes_avx2(trits: &[Trit], bytes: &mut [Byte]) -> usize {
+        let src = trits.as_ptr().cast::<u8>();
+        let dst = bytes.as_mut_ptr();
+        let mut codes = [0_u8; 112];
+
+        let mut i = 0;
+        while 9 * i / 2 + 292 <= trits.len() && i + 64 <= bytes.len() {
+            for k in 0..3 {
+                let codes_32 = tryte_codes_avx2(src.add(9 * i / 2 + 96 * k));
+                _mm256_storeu_si256(codes.as_mut_ptr().add(32 * k).cast(), codes_32);
+            }
+            // The low halves hold the first 4 groups of 4 pairs, the high halves the last 4.
+            let mut pairs = [_mm256_setzero_si256(); 4];
+            for (k, pair) in pairs.iter_mut().enumerate() {
+                let codes = codes.as_ptr().add(12 * k);
+                *pair = bytes_of_codes_avx2(expand_avx2(load_halves(codes, codes.add(48))));
+            }
+            let pairs_01 = _mm256_packs_epi32(pairs[0], pairs[1]);
+            let pairs_23 = _mm256_packs_epi32(pairs[2], pairs[3]);
+            let low = _mm256_permute2x128_si256(pairs_01, pairs_23, 0x20);
+            let high = _mm256_permute2x128_si256(pairs_01, pairs_23, 0x31);
+            _mm256_storeu_si256(dst.add(i).cast(), low);
+            _mm256_storeu_si256(dst.add(i + 32).cast(), high);
+            i += 64;
+        }
+        i
+    }
+
+    /// Converts 32 bytes per iteration.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn bytes_to_trytes_avx2(bytes: &[Byte], trytes: &mut [Tryte]) -> usize {
+        let src = bytes.as_ptr();
+        let dst = trytes.as_mut_ptr().cast::<u8>();
+
+        let mut i = 0;
+        while i + 32 <= bytes.len() && 3 * i / 2 + 52 <= trytes.len() {
+            let pairs = _mm256_loadu_si256(src.add(i).cast());
+            let c0 = _mm256_and_si256(_mm256_srli_epi16(pairs, 3), _mm256_set1_epi16(0x1F));
+            let c1 = _mm256_srli_epi16(pairs, 11);
+            let c2 = _mm256_or_si256(
+                _mm256_and_si256(pairs, _mm256_set1_epi16(0x07)),
+                _mm256_and_si256(_mm256_srli_epi16(pairs, 5), _mm256_set1_epi16(0x38)),
+            );
+            let c01 = _mm256_or_si256(c0, _mm256_slli_epi16(c1, 8));
+            let groups = [
+                ascii_of_codes_avx2(compact_avx2(_mm256_unpacklo_epi16(c01, c2))),
+                ascii_of_codes_avx2(compact_avx2(_mm256_unpackhi_epi16(c01, c2))),
+            ];
+            for (k, group) in groups.iter().enumerate() {
+                _mm_storeu_si128(
+                    dst.add(3 * i / 2 + 12 * k).cast(),
+                    _mm256_castsi256_si128(*group),
+                );
+            }
+            for (k, group) in groups.iter().enumerate() {
+                let high = _mm256_extracti128_si256(*group, 1);
+                _mm_storeu_si128(dst.add(3 * i / 2 + 24 + 12 * k).cast(), high);
+            }
+            i += 32;
+        }
+        i
+    }
+
+    /// Returns the balanced trits `[t0, t1, t2]` of 32 trytes, see `trits_of_sse2`.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn trits_of_avx2(trytes: __m256i) -> [__m256i; 3] {
+        let code = _mm256_sub_epi8(trytes, _mm256_set1_epi8(ASCII_A_MINUS_1));
+        let high = _mm256_cmpgt_epi8(code, _mm256_set1_epi8(13));
+        let value = _mm256_sub_epi8(code, _mm256_and_si256(high, _mm256_set1_epi8(27)));
+        let value =
+            _mm256_andnot_si256(_mm256_cmpeq_epi8(trytes, _mm256_set1_epi8(ASCII_9)), value);
+
+        let rest = _mm256_add_epi8(value, _mm256_set1_epi8(13));
+        let (t2, rest) = split_digit_avx2(rest, 9);
+        let (t1, rest) = split_digit_avx2(rest, 3);
+        let (t0, _) = split_digit_avx2(rest, 1);
+        [t0, t1, t2]
+    }
+
+    /// See `split_digit_sse2`.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn split_digit_avx2(rest: __m256i, weight: i8) -> (__m256i, __m256i) {
+        let ge1 = _mm256_cmpgt_epi8(rest, _mm256_set1_epi8(weight - 1));
+        let ge2 = _mm256_cmpgt_epi8(rest, _mm256_set1_epi8(2 * weight - 1));
+        let trit = _mm256_sub_epi8(_mm256_sub_epi8(_mm256_set1_epi8(-1), ge1), ge2);
+        let weight = _mm256_set1_epi8(weight);
+        let rest = _mm256_sub_epi8(
+            _mm256_sub_epi8(rest, _mm256_and_si256(ge1, weight)),
+            _mm256_and_si256(ge2, weight),
+        );
+        (trit, rest)
+    }
+
+    /// Returns the tryte codes of the 32 trytes of the 96 trits at `src`, reading 100 trits.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn tryte_codes_avx2(src: *const u8) -> __m256i {
+        let mut codes = [_mm256_setzero_si256(); 4];
+        for (k, code) in codes.iter_mut().enumerate() {
+            let trits = load_halves(src.add(12 * k), src.add(48 + 12 * k));
+            *code = codes_of_trits_avx2(expand_avx2(trits));
+        }
+        _mm256_packus_epi16(
+            _mm256_packs_epi32(codes[0], codes[1]),
+            _mm256_packs_epi32(codes[2], codes[3]),
+        )
+    }
+
+    /// See `codes_of_trits_sse2`.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn codes_of_trits_avx2(trits: __m256i) -> __m256i {
+        let t1 = _mm256_srli_epi32(trits, 8);
+        let t2 = _mm256_srli_epi32(trits, 16);
+        let t1_3 = _mm256_add_epi8(_mm256_add_epi8(t1, t1), t1);
+        let t2_3 = _mm256_add_epi8(_mm256_add_epi8(t2, t2), t2);
+        let t2_9 = _mm256_add_epi8(_mm256_add_epi8(t2_3, t2_3), t2_3);
+        let value = _mm256_add_epi8(_mm256_add_epi8(trits, t1_3), t2_9);
+        let negative = _mm256_cmpgt_epi8(_mm256_setzero_si256(), value);
+        let code = _mm256_add_epi8(value, _mm256_and_si256(negative, _mm256_set1_epi8(27)));
+        _mm256_and_si256(code, _mm256_set1_epi32(0xFF))
+    }
+
+    /// See `bytes_of_codes_sse2`.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn bytes_of_codes_avx2(codes: __m256i) -> __m256i {
+        let c2 = _mm256_srli_epi32(codes, 16);
+        let c01 = _mm256_slli_epi16(_mm256_and_si256(codes, _mm256_set1_epi32(0xFFFF)), 3);
+        let c2 = _mm256_or_si256(
+            _mm256_and_si256(c2, _mm256_set1_epi32(0x07)),
+            _mm256_slli_epi32(_mm256_srli_epi32(c2, 3), 8),
+        );
+        _mm256_srai_epi32(_mm256_slli_epi32(_mm256_or_si256(c01, c2), 16), 16)
+    }
+
+    /// See `ascii_of_codes_sse2`.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn ascii_of_codes_avx2(codes: __m256i) -> __m256i {
+        let nines = _mm256_cmpeq_epi8(codes, _mm256_setzero_si256());
+        _mm256_sub_epi8(
+            _mm256_add_epi8(codes, _mm256_set1_epi8(ASCII_A_MINUS_1)),
+            _mm256_and_si256(nines, _mm256_set1_epi8(ASCII_A_MINUS_1 - ASCII_9)),
+        )
+    }
+
+    /// See `expand_sse2`, applied to both 128 bit halves.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn expand_avx2(bytes: __m256i) -> __m256i {
+        let halves = _mm256_unpacklo_epi64(bytes, _mm256_srli_si256(bytes, 6));
+        let even = _mm256_and_si256(halves, _mm256_set1_epi64x(0xFF_FFFF));
+        let odd =
+            _mm256_and_si256(_mm256_slli_epi64(halves, 8), _mm256_set1_epi64x(0xFF_FFFF_0000_0000));
+        _mm256_or_si256(even, odd)
+    }
+
+    /// See `compact_sse2`, applied to both 128 bit halves.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn compact_avx2(lanes: __m256i) -> __m256i {
+        let even = _mm256_and_si256(lanes, _mm256_set1_epi64x(0xFF_FFFF));
+        let odd =
+            _mm256_and_si256(_mm256_srli_epi64(lanes, 8), _mm256_set1_epi64x(0xFFFF_FF00_0000));
+        let halves = _mm256_or_si256(even, odd);
+        _mm256_or_si256(
+            _mm256_and_si256(halves, _mm256_set_epi64x(0, -1, 0, -1)),
+            _mm256_slli_si256(_mm256_srli_si256(halves, 8), 6),
+        )
+    }
+
+    /// Loads 16 bytes from each `low` and `high` into the respective 128 bit half.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn load_halves(low: *const u8, high: *const u8) -> __m256i {
+        _mm256_inserti128_si256(
+            _mm256_castsi128_si256(_mm_loadu_si128(low.cast())),
+            _mm_loadu_si128(high.cast()),
+            1,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::luts::TRYTE_CODE_TO_ASCII_CODE;
+    use rand::{thread_rng, Rng};
+
+    const LENGTHS: [usize; 6] = [0, 3, 99, 150, 297, 2673];
+
+    fn random_trytes(len: usize) -> Vec<Tryte> {
+        (0..len).map(|_| TRYTE_CODE_TO_ASCII_CODE[thread_rng().gen_range(0, 27)]).collect()
+    }
+
+    fn expected_trits(trytes: &[Tryte]) -> Vec<Trit> {
+        let mut trits = vec![Trit::Zero; trytes.len() * 3];
+        trits::write_from_trytes(trytes, &mut trits);
+        trits
+    }
+
+    fn expected_bytes(trits: &[Trit]) -> Vec<Byte> {
+        let mut bytes = vec![0; trits.len() / 9 * 2];
+        bytes::write_from_trits(trits, &mut bytes);
+        bytes
+    }
+
+    fn expected_trytes(bytes: &[Byte]) -> Vec<Tryte> {
+        let mut trytes = vec![Tryte::default(); bytes.len() / 2 * 3];
+        trytes::write_from_bytes(bytes, &mut trytes);
+        trytes
+    }
+
+    #[test]
+    fn test_trytes_to_trits() {
+        for &len in LENGTHS.iter() {
+            let trytes = random_trytes(len);
+            let mut trits = vec![Trit::Pos; len * 3];
+            trytes_to_trits(&trytes, &mut trits);
+            assert_eq!(expected_trits(&trytes), trits);
+        }
+    }
+
+    #[test]
+    fn test_trits_to_bytes() {
+        for &len in LENGTHS.iter() {
+            let trits = expected_trits(&random_trytes(len));
+            let mut bytes = vec![0xFF; len / 3 * 2];
+            trits_to_bytes(&trits, &mut bytes);
+            assert_eq!(expected_bytes(&trits), bytes);
+        }
+    }
+
+    #[test]
+    fn test_bytes_to_trytes() {
+        for &len in LENGTHS.iter() {
+            let bytes = expected_bytes(&expected_trits(&random_trytes(len)));
+            let mut trytes = vec![Tryte::default(); len];
+            bytes_to_trytes(&bytes, &mut trytes);
+            assert_eq!(expected_trytes(&bytes), trytes);
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    type Kernels = (
+        unsafe fn(&[Tryte], &mut [Trit]) -> usize,
+        unsafe fn(&[Trit], &mut [Byte]) -> usize,
+        unsafe fn(&[Byte], &mut [Tryte]) -> usize,
+    );
+
+    /// Returns the kernels supported by this CPU.
+    #[cfg(target_arch = "x86_64")]
+    fn x86_kernels() -> Vec<Kernels> {
+        let mut kernels: Vec<Kernels> =
+            vec![(x86::trytes_to_trits_sse2, x86::trits_to_bytes_sse2, x86::bytes_to_trytes_sse2)];
+        if x86::has_avx2() {
+            kernels.push((
+                x86::trytes_to_trits_avx2,
+                x86::trits_to_bytes_avx2,
+                x86::bytes_to_trytes_avx2,
+            ));
+        }
+        kernels
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_x86_kernels() {
+        let kernels = x86_kernels();
+
+        // Every tryte, with and without the kernels' slack at the end.
+        let all: Vec<Tryte> = TRYTE_CODE_TO_ASCII_CODE.iter().cycle().take(2700).copied().collect();
+        for trytes in [random_trytes(2673), all[..2673].to_vec(), all].iter() {
+            let trits = expected_trits(trytes);
+            let bytes = expected_bytes(&trits);
+
+            for (to_trits, to_bytes, to_trytes) in kernels.iter() {
+                let mut out = vec![Trit::Pos; trits.len()];
+                let done = unsafe { to_trits(trytes, &mut out) };
+                assert!(done > 0);
+                assert_eq!(trits[..done * 3], out[..done * 3]);
+
+                let mut out = vec![0xFF; bytes.len()];
+                let done = unsafe { to_bytes(&trits, &mut out) };
+                assert!(done > 0);
+                assert_eq!(bytes[..done], out[..done]);
+
+                let mut out = vec![Tryte::default(); trytes.len()];
+                let done = unsafe { to_trytes(&bytes, &mut out) };
+                assert!(done > 0);
+                assert_eq!(trytes[..done / 2 * 3], out[..done / 2 * 3]);
+            }
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_x86_kernels_invalid_trytes() {
+        // Invalid trytes can only be built inside the crate, but must not produce invalid trits.
+        for &byte in [0_u8, 0x20, 0x7F, 0x80, 0xFF].iter() {
+            let trytes = vec![Tryte(byte); 64];
+            for (to_trits, _, _) in x86_kernels().iter() {
+                let mut trits = vec![Trit::Zero; 3 * trytes.len()];
+                let done = unsafe { to_trits(&trytes, &mut trits) };
+                assert!(done > 0);
+                assert!(trits.iter().all(|&t| (-1..=1).contains(&(t as i8))));
+            }
+        }
+    }
+}
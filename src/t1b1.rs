@@ -0,0 +1,83 @@
+//! Converter functions for the T1B1 encoding, which stores every trit in a byte of its own.
+//!
+//! Each trit is stored as a two's complement `i8`, i.e. `-1` becomes `0xFF`.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{
+    encoding::Balanced,
+    error::ConversionError,
+    types::Trit,
+};
+
+/// The number of trits packed into a single byte.
+const TRITS_PER_BYTE: usize = 1;
+
+/// The T1B1 encoding, for code that is generic over `TritEncoding`s.
+pub type T1B1 = Balanced<TRITS_PER_BYTE>;
+
+/// Returns the number of bytes needed to pack `num_trits` trits.
+pub const fn packed_len(num_trits: usize) -> usize {
+    T1B1::packed_len(num_trits)
+}
+
+/// Encodes arbitrary slices of trits.
+#[cfg(feature = "alloc")]
+pub fn from_trits(trits: &[Trit]) -> Vec<u8> {
+    T1B1::from_trits(trits)
+}
+
+/// Encodes arbitrary slices of trits, writing the bytes into `packed`.
+///
+/// Returns the number of bytes written.
+pub fn from_trits_into(trits: &[Trit], packed: &mut [u8]) -> Result<usize, ConversionError> {
+    T1B1::from_trits_into(trits, packed)
+}
+
+/// Decodes `num_trits` trits.
+///
+/// This function will panic if `packed` doesn't hold exactly `num_trits` trits.
+#[cfg(feature = "alloc")]
+pub fn to_trits(packed: &[u8], num_trits: usize) -> Vec<Trit> {
+    T1B1::to_trits(packed, num_trits)
+}
+
+/// Tries to decode `num_trits` trits.
+#[cfg(feature = "alloc")]
+pub fn try_to_trits(packed: &[u8], num_trits: usize) -> Result<Vec<Trit>, ConversionError> {
+    T1B1::try_to_trits(packed, num_trits)
+}
+
+/// Decodes `num_trits` trits, writing them into `trits`.
+///
+/// Returns the number of trits written.
+pub fn to_trits_into(
+    packed: &[u8],
+    num_trits: usize,
+    trits: &mut [Trit],
+) -> Result<usize, ConversionError> {
+    T1B1::to_trits_into(packed, num_trits, trits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encoding::TritEncoding, error::ErrorKind};
+
+    #[test]
+    fn test_from_trits() {
+        assert_eq!(vec![255, 0, 1], from_trits(&crate::trits::from_raw(&[-1, 0, 1])));
+    }
+
+    #[test]
+    fn test_to_trits() {
+        let trits = crate::trits::from_raw(&[1, -1, 1, 0, 1, 0, -1]);
+        assert_eq!(trits, to_trits(&from_trits(&trits), trits.len()));
+        assert_eq!(trits, T1B1::decode(&T1B1::encode(&trits).unwrap(), trits.len()).unwrap());
+
+        let err = try_to_trits(&[1, 2], 2).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(1, err.index());
+    }
+}
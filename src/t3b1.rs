@@ -0,0 +1,236 @@
+//! Converter functions for the T3B1 encoding, which packs 3 trits (a tryte) into a single byte.
+//!
+//! Each byte holds the tryte code of its trits, i.e. a number in the range `0..=26` where `0` is
+//! the tryte `9`, `1` is `A` and `26` is `Z`. If the number of trits is not a multiple of 3, the
+//! last byte is padded with zero trits.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{
+    encoding::take_trits,
+    encoding::TritEncoding,
+    error::ConversionError,
+    error::ErrorKind,
+    luts::ASCII_CODE_OFFSET,
+    luts::ASCII_CODE_TO_TRYTE_CODE,
+    luts::TRYTE_CODE_TO_ASCII_CODE,
+    luts::TRYTE_CODE_TO_TRITS,
+    types::Trit,
+    types::Tryte,
+    util::check_buf_len,
+    util::check_len,
+};
+
+/// The number of trits packed into a single byte.
+const TRITS_PER_BYTE: usize = 3;
+
+/// The largest valid byte.
+const MAX_TRYTE_CODE: u8 = 26;
+
+/// Returns the number of bytes needed to pack `num_trits` trits.
+pub const fn packed_len(num_trits: usize) -> usize {
+    num_trits.div_ceil(TRITS_PER_BYTE)
+}
+
+/// The T3B1 encoding, for code that is generic over `TritEncoding`s.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct T3B1;
+
+impl TritEncoding for T3B1 {
+    fn packed_len(num_trits: usize) -> usize {
+        packed_len(num_trits)
+    }
+
+    fn encode_into(trits: &[Trit], bytes: &mut [u8]) -> Result<usize, ConversionError> {
+        from_trits_into(trits, bytes)
+    }
+
+    fn decode_into(
+        bytes: &[u8],
+        num_trits: usize,
+        trits: &mut [Trit],
+    ) -> Result<usize, ConversionError> {
+        to_trits_into(bytes, num_trits, trits)
+    }
+}
+
+/// Encodes arbitrary slices of trits.
+#[cfg(feature = "alloc")]
+pub fn from_trits(trits: &[Trit]) -> Vec<u8> {
+    let mut packed = vec![0; packed_len(trits.len())];
+    pack(trits.iter().cloned(), &mut packed);
+    packed
+}
+
+/// Encodes arbitrary slices of trits, writing the bytes into `packed`.
+///
+/// Returns the number of bytes written.
+pub fn from_trits_into(trits: &[Trit], packed: &mut [u8]) -> Result<usize, ConversionError> {
+    let len = packed_len(trits.len());
+    check_buf_len(packed.len(), len)?;
+
+    pack(trits.iter().cloned(), &mut packed[..len]);
+    Ok(len)
+}
+
+/// Encodes trytes, one byte per tryte.
+#[cfg(feature = "alloc")]
+pub fn from_trytes(trytes: &[Tryte]) -> Vec<u8> {
+    trytes.iter().map(|&t| tryte_code(t)).collect()
+}
+
+/// Encodes trytes, writing the bytes into `packed`.
+///
+/// Returns the number of bytes written.
+pub fn from_trytes_into(trytes: &[Tryte], packed: &mut [u8]) -> Result<usize, ConversionError> {
+    check_buf_len(packed.len(), trytes.len())?;
+
+    packed.iter_mut().zip(trytes).for_each(|(b, &t)| *b = tryte_code(t));
+    Ok(trytes.len())
+}
+
+/// Decodes `num_trits` trits.
+///
+/// This function will panic if `packed` doesn't hold exactly `num_trits` trits.
+#[cfg(feature = "alloc")]
+pub fn to_trits(packed: &[u8], num_trits: usize) -> Vec<Trit> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_packed(packed, num_trits).unwrap();
+    }
+
+    unpack(packed).take(num_trits).collect()
+}
+
+/// Tries to decode `num_trits` trits.
+#[cfg(feature = "alloc")]
+pub fn try_to_trits(packed: &[u8], num_trits: usize) -> Result<Vec<Trit>, ConversionError> {
+    check_packed(packed, num_trits)?;
+
+    Ok(unpack(packed).take(num_trits).collect())
+}
+
+/// Decodes `num_trits` trits, writing them into `trits`.
+///
+/// Returns the number of trits written.
+pub fn to_trits_into(
+    packed: &[u8],
+    num_trits: usize,
+    trits: &mut [Trit],
+) -> Result<usize, ConversionError> {
+    check_packed(packed, num_trits)?;
+    check_buf_len(trits.len(), num_trits)?;
+
+    trits[..num_trits].iter_mut().zip(unpack(packed)).for_each(|(t, u)| *t = u);
+    Ok(num_trits)
+}
+
+/// Decodes trytes, one tryte per byte.
+///
+/// This function will panic if a byte is not a tryte code.
+#[cfg(feature = "alloc")]
+pub fn to_trytes(packed: &[u8]) -> Vec<Tryte> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_tryte_codes(packed).unwrap();
+    }
+
+    packed.iter().map(|&b| TRYTE_CODE_TO_ASCII_CODE[b as usize]).collect()
+}
+
+/// Tries to decode trytes, one tryte per byte.
+#[cfg(feature = "alloc")]
+pub fn try_to_trytes(packed: &[u8]) -> Result<Vec<Tryte>, ConversionError> {
+    check_tryte_codes(packed)?;
+
+    Ok(packed.iter().map(|&b| TRYTE_CODE_TO_ASCII_CODE[b as usize]).collect())
+}
+
+/// Decodes trytes, writing them into `trytes`.
+///
+/// Returns the number of trytes written.
+pub fn to_trytes_into(packed: &[u8], trytes: &mut [Tryte]) -> Result<usize, ConversionError> {
+    check_tryte_codes(packed)?;
+    check_buf_len(trytes.len(), packed.len())?;
+
+    trytes.iter_mut().zip(packed).for_each(|(t, &b)| *t = TRYTE_CODE_TO_ASCII_CODE[b as usize]);
+    Ok(packed.len())
+}
+
+#[inline]
+fn tryte_code(tryte: Tryte) -> u8 {
+    ASCII_CODE_TO_TRYTE_CODE[(tryte.0 - ASCII_CODE_OFFSET) as usize]
+}
+
+#[inline]
+fn pack<I>(mut trits: I, packed: &mut [u8])
+where
+    I: Iterator<Item = Trit>,
+{
+    for byte in packed.iter_mut() {
+        let [t0, t1, t2]: [Trit; TRITS_PER_BYTE] = take_trits(&mut trits);
+        let index = t0 as i8 + 3 * t1 as i8 + 9 * t2 as i8;
+
+        *byte = if index < 0 { index + 27 } else { index } as u8;
+    }
+}
+
+#[inline]
+fn unpack(packed: &[u8]) -> impl Iterator<Item = Trit> + '_ {
+    packed.iter().flat_map(|&b| TRYTE_CODE_TO_TRITS[b as usize])
+}
+
+/// Makes sure `packed` holds exactly `num_trits` trits and only tryte codes.
+fn check_packed(packed: &[u8], num_trits: usize) -> Result<(), ConversionError> {
+    check_len(packed.len(), packed_len(num_trits))?;
+    check_tryte_codes(packed)
+}
+
+fn check_tryte_codes(packed: &[u8]) -> Result<(), ConversionError> {
+    match packed.iter().position(|&b| b > MAX_TRYTE_CODE) {
+        Some(index) => Err(ConversionError::new(ErrorKind::OutOfRange, index)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_trits() {
+        assert_eq!(vec![13, 14, 1], from_trits(&crate::trits::from_raw(&[1, 1, 1, -1, -1, -1, 1])));
+        assert_eq!(from_trytes(&crate::trytes::from_tryte_str("9AMNZ")), vec![0, 1, 13, 14, 26]);
+    }
+
+    #[test]
+    fn test_to_trits() {
+        let trits = crate::trits::from_raw(&[1, -1, 1, 0, 1, 0, -1]);
+        assert_eq!(trits, to_trits(&from_trits(&trits), trits.len()));
+        assert_eq!(trits, T3B1::decode(&T3B1::encode(&trits).unwrap(), trits.len()).unwrap());
+
+        let err = try_to_trits(&[1, 27], 6).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(1, err.index());
+    }
+
+    #[test]
+    fn test_to_trits_into() {
+        // The padding trits aren't written past `num_trits`.
+        let mut trits = [Trit::Neg; 8];
+        assert_eq!(Ok(4), to_trits_into(&from_trits(&[Trit::Pos; 4]), 4, &mut trits));
+        assert_eq!([Trit::Pos; 4], trits[..4]);
+        assert_eq!([Trit::Neg; 4], trits[4..]);
+    }
+
+    #[test]
+    fn test_to_trytes() {
+        let trytes = crate::trytes::from_tryte_str("RBTC9D9DCDEAKDCDFD9DSCFA");
+        assert_eq!(trytes, to_trytes(&from_trytes(&trytes)));
+
+        let mut buf = [Tryte::default(); 4];
+        let err = to_trytes_into(&from_trytes(&trytes), &mut buf).unwrap_err();
+        assert_eq!(ErrorKind::BufferTooSmall, err.kind());
+    }
+}
@@ -0,0 +1,103 @@
+//! Converter functions for the T4B1 encoding, which packs 4 trits into a single byte.
+//!
+//! The 4 trits of a byte are read as a balanced ternary number, least significant trit first,
+//! which is stored as a two's complement `i8` in the range `-40..=40`. If the number of trits is
+//! not a multiple of 4, the last byte is padded with zero trits.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{
+    encoding::Balanced,
+    error::ConversionError,
+    types::Trit,
+};
+
+/// The number of trits packed into a single byte.
+const TRITS_PER_BYTE: usize = 4;
+
+/// The T4B1 encoding, for code that is generic over `TritEncoding`s.
+pub type T4B1 = Balanced<TRITS_PER_BYTE>;
+
+/// Returns the number of bytes needed to pack `num_trits` trits.
+pub const fn packed_len(num_trits: usize) -> usize {
+    T4B1::packed_len(num_trits)
+}
+
+/// Encodes arbitrary slices of trits.
+#[cfg(feature = "alloc")]
+pub fn from_trits(trits: &[Trit]) -> Vec<u8> {
+    T4B1::from_trits(trits)
+}
+
+/// Encodes arbitrary slices of trits, writing the bytes into `packed`.
+///
+/// Returns the number of bytes written.
+pub fn from_trits_into(trits: &[Trit], packed: &mut [u8]) -> Result<usize, ConversionError> {
+    T4B1::from_trits_into(trits, packed)
+}
+
+/// Decodes `num_trits` trits.
+///
+/// This function will panic if `packed` doesn't hold exactly `num_trits` trits.
+#[cfg(feature = "alloc")]
+pub fn to_trits(packed: &[u8], num_trits: usize) -> Vec<Trit> {
+    T4B1::to_trits(packed, num_trits)
+}
+
+/// Tries to decode `num_trits` trits.
+#[cfg(feature = "alloc")]
+pub fn try_to_trits(packed: &[u8], num_trits: usize) -> Result<Vec<Trit>, ConversionError> {
+    T4B1::try_to_trits(packed, num_trits)
+}
+
+/// Decodes `num_trits` trits, writing them into `trits`.
+///
+/// Returns the number of trits written.
+pub fn to_trits_into(
+    packed: &[u8],
+    num_trits: usize,
+    trits: &mut [Trit],
+) -> Result<usize, ConversionError> {
+    T4B1::to_trits_into(packed, num_trits, trits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encoding::TritEncoding, error::ErrorKind};
+
+    #[test]
+    fn test_from_trits() {
+        assert_eq!(vec![40], from_trits(&[Trit::Pos; 4]));
+        assert_eq!(vec![216], from_trits(&[Trit::Neg; 4]));
+        assert_eq!(vec![7, 1], from_trits(&crate::trits::from_raw(&[1, -1, 1, 0, 1])));
+    }
+
+    #[test]
+    fn test_to_trits() {
+        let trits = crate::trits::from_raw(&[1, -1, 1, 0, 1, 0, -1]);
+        assert_eq!(trits, to_trits(&from_trits(&trits), trits.len()));
+        assert_eq!(trits, T4B1::decode(&T4B1::encode(&trits).unwrap(), trits.len()).unwrap());
+
+        let err = try_to_trits(&[41], 4).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(0, err.index());
+
+        // The 3 trits padding the last byte aren't zero.
+        let err = try_to_trits(&[7, 40], 5).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(1, err.index());
+        assert!(try_to_trits(&[7, -1_i8 as u8], 5).is_ok());
+        assert!(try_to_trits(&[7, 2], 5).is_err());
+    }
+
+    #[test]
+    fn test_to_trits_into() {
+        // The padding trits aren't written past `num_trits`.
+        let mut trits = [Trit::Neg; 10];
+        assert_eq!(Ok(5), to_trits_into(&from_trits(&[Trit::Pos; 5]), 5, &mut trits));
+        assert_eq!([Trit::Pos; 5], trits[..5]);
+        assert_eq!([Trit::Neg; 5], trits[5..]);
+    }
+}
@@ -0,0 +1,424 @@
+//! Converter functions for the T5B1 encoding, which packs 5 trits into a single byte.
+//!
+//! The 5 trits of a byte are read as a balanced ternary number, least significant trit first,
+//! which is stored as a two's complement `i8` in the range `-121..=121`. If the number of trits is
+//! not a multiple of 5, the last byte is padded with zero trits.
+//!
+//! Since the padding can't be told apart from encoded zero trits, all functions decoding T5B1
+//! bytes need to know how many trits, trytes or 9/2 bytes were encoded, and fail if the padding
+//! trits aren't zero.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{
+    constants::SIG_MSG_FRG_SIZE_TRITS,
+    constants::TRANSACTION_SIZE_TRITS,
+    encoding::pack_balanced,
+    encoding::take_trits,
+    encoding::unpack_balanced,
+    encoding::Balanced,
+    error::ConversionError,
+    trits::from_tryte,
+    trytes::from_trit_triplet,
+    types::Trit,
+    types::Tryte,
+    util::as_array,
+    util::assert_packed_len,
+    util::check_buf_len,
+    util::check_bytes,
+    util::check_len_multiple_of,
+};
+
+/// The number of trits packed into a single byte.
+const TRITS_PER_BYTE: usize = 5;
+
+/// The T5B1 encoding, for code that is generic over `TritEncoding`s.
+pub type T5B1 = Balanced<TRITS_PER_BYTE>;
+
+/// Returns the number of bytes needed to pack `num_trits` trits.
+pub const fn packed_len(num_trits: usize) -> usize {
+    T5B1::packed_len(num_trits)
+}
+
+/// Encodes arbitrary slices of trits.
+#[cfg(feature = "alloc")]
+pub fn from_trits(trits: &[Trit]) -> Vec<u8> {
+    T5B1::from_trits(trits)
+}
+
+/// Encodes arbitrary slices of trits, writing the bytes into `packed`.
+///
+/// Returns the number of bytes written.
+pub fn from_trits_into(trits: &[Trit], packed: &mut [u8]) -> Result<usize, ConversionError> {
+    T5B1::from_trits_into(trits, packed)
+}
+
+/// Encodes an array of trits.
+///
+/// `M` has to be `ceil(N / 5)`, which is checked at compile time.
+pub fn from_trits_array<const N: usize, const M: usize>(trits: &[Trit; N]) -> [u8; M] {
+    assert_packed_len::<N, M, TRITS_PER_BYTE>();
+
+    let mut packed = [0; M];
+    pack(trits.iter().cloned(), &mut packed);
+    packed
+}
+
+macro_rules! from_trits_conv {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
+        /// Encodes fixed-sized slices of trits.
+        pub fn $func_name(trits: &[Trit]) -> [u8; packed_len($length)] {
+            from_trits_array::<$length, { packed_len($length) }>(as_array(trits).unwrap())
+        }
+
+        /// Tries to encode fixed-sized slices of trits.
+        pub fn $try_func_name(
+            trits: &[Trit],
+        ) -> Result<[u8; packed_len($length)], ConversionError> {
+            Ok(from_trits_array::<$length, { packed_len($length) }>(as_array(trits)?))
+        }
+    };
+}
+
+from_trits_conv!(from_trits_all, try_from_trits_all, TRANSACTION_SIZE_TRITS);
+from_trits_conv!(from_trits_sig, try_from_trits_sig, SIG_MSG_FRG_SIZE_TRITS);
+from_trits_conv!(from_trits_243, try_from_trits_243, 243);
+
+/// Encodes arbitrary slices of trytes.
+#[cfg(feature = "alloc")]
+pub fn from_trytes(trytes: &[Tryte]) -> Vec<u8> {
+    let mut packed = vec![0; packed_len(trytes.len() * 3)];
+    pack(trytes.iter().flat_map(|&t| from_tryte(t)), &mut packed);
+    packed
+}
+
+/// Encodes arbitrary slices of trytes, writing the bytes into `packed`.
+///
+/// Returns the number of bytes written.
+pub fn from_trytes_into(trytes: &[Tryte], packed: &mut [u8]) -> Result<usize, ConversionError> {
+    let len = packed_len(trytes.len() * 3);
+    check_buf_len(packed.len(), len)?;
+
+    pack(trytes.iter().flat_map(|&t| from_tryte(t)), &mut packed[..len]);
+    Ok(len)
+}
+
+/// Re-encodes arbitrary slices of 9/2 encoded bytes.
+#[cfg(feature = "alloc")]
+pub fn from_bytes(bytes: &[u8]) -> Vec<u8> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_len_multiple_of(bytes.len(), 2).unwrap();
+        check_bytes(bytes).unwrap();
+    }
+
+    let mut packed = vec![0; packed_len(bytes.len() / 2 * 9)];
+    pack(unpack_9_2(bytes), &mut packed);
+    packed
+}
+
+/// Tries to re-encode arbitrary slices of 9/2 encoded bytes.
+#[cfg(feature = "alloc")]
+pub fn try_from_bytes(bytes: &[u8]) -> Result<Vec<u8>, ConversionError> {
+    check_len_multiple_of(bytes.len(), 2)?;
+    check_bytes(bytes)?;
+
+    let mut packed = vec![0; packed_len(bytes.len() / 2 * 9)];
+    pack(unpack_9_2(bytes), &mut packed);
+    Ok(packed)
+}
+
+/// Re-encodes arbitrary slices of 9/2 encoded bytes, writing the bytes into `packed`.
+///
+/// Returns the number of bytes written.
+pub fn from_bytes_into(bytes: &[u8], packed: &mut [u8]) -> Result<usize, ConversionError> {
+    check_len_multiple_of(bytes.len(), 2)?;
+    check_bytes(bytes)?;
+
+    let len = packed_len(bytes.len() / 2 * 9);
+    check_buf_len(packed.len(), len)?;
+
+    pack(unpack_9_2(bytes), &mut packed[..len]);
+    Ok(len)
+}
+
+/// Decodes `num_trits` trits.
+///
+/// This function will panic if `packed` doesn't hold exactly `num_trits` trits.
+#[cfg(feature = "alloc")]
+pub fn to_trits(packed: &[u8], num_trits: usize) -> Vec<Trit> {
+    T5B1::to_trits(packed, num_trits)
+}
+
+/// Tries to decode `num_trits` trits.
+#[cfg(feature = "alloc")]
+pub fn try_to_trits(packed: &[u8], num_trits: usize) -> Result<Vec<Trit>, ConversionError> {
+    T5B1::try_to_trits(packed, num_trits)
+}
+
+/// Decodes `num_trits` trits, writing them into `trits`.
+///
+/// Returns the number of trits written.
+pub fn to_trits_into(
+    packed: &[u8],
+    num_trits: usize,
+    trits: &mut [Trit],
+) -> Result<usize, ConversionError> {
+    T5B1::to_trits_into(packed, num_trits, trits)
+}
+
+/// Decodes an array of bytes to an array of trits.
+///
+/// `M` has to be `ceil(N / 5)`, which is checked at compile time.
+pub fn to_trits_array<const N: usize, const M: usize>(packed: &[u8; M]) -> [Trit; N] {
+    assert_packed_len::<N, M, TRITS_PER_BYTE>();
+
+    #[cfg(not(feature = "no_checks"))]
+    {
+        T5B1::check_packed(packed, N).unwrap();
+    }
+
+    let mut trits = [Trit::Zero; N];
+    unpack_into(packed, &mut trits);
+    trits
+}
+
+/// Tries to decode an array of bytes to an array of trits.
+///
+/// `M` has to be `ceil(N / 5)`, which is checked at compile time.
+pub fn try_to_trits_array<const N: usize, const M: usize>(
+    packed: &[u8; M],
+) -> Result<[Trit; N], ConversionError> {
+    assert_packed_len::<N, M, TRITS_PER_BYTE>();
+
+    T5B1::check_packed(packed, N)?;
+
+    let mut trits = [Trit::Zero; N];
+    unpack_into(packed, &mut trits);
+    Ok(trits)
+}
+
+macro_rules! to_trits_conv {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
+        /// Decodes fixed-sized slices of bytes to trits.
+        pub fn $func_name(packed: &[u8]) -> [Trit; $length] {
+            to_trits_array::<$length, { packed_len($length) }>(as_array(packed).unwrap())
+        }
+
+        /// Tries to decode fixed-sized slices of bytes to trits.
+        pub fn $try_func_name(packed: &[u8]) -> Result<[Trit; $length], ConversionError> {
+            try_to_trits_array::<$length, { packed_len($length) }>(as_array(packed)?)
+        }
+    };
+}
+
+to_trits_conv!(to_trits_all, try_to_trits_all, TRANSACTION_SIZE_TRITS);
+to_trits_conv!(to_trits_sig, try_to_trits_sig, SIG_MSG_FRG_SIZE_TRITS);
+to_trits_conv!(to_trits_243, try_to_trits_243, 243);
+
+/// Decodes `num_trytes` trytes.
+///
+/// This function will panic if `packed` doesn't hold exactly `num_trytes` trytes.
+#[cfg(feature = "alloc")]
+pub fn to_trytes(packed: &[u8], num_trytes: usize) -> Vec<Tryte> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        T5B1::check_packed(packed, num_trytes * 3).unwrap();
+    }
+
+    let mut trytes = vec![Tryte::default(); num_trytes];
+    write_trytes(packed, &mut trytes);
+    trytes
+}
+
+/// Tries to decode `num_trytes` trytes.
+#[cfg(feature = "alloc")]
+pub fn try_to_trytes(packed: &[u8], num_trytes: usize) -> Result<Vec<Tryte>, ConversionError> {
+    T5B1::check_packed(packed, num_trytes * 3)?;
+
+    let mut trytes = vec![Tryte::default(); num_trytes];
+    write_trytes(packed, &mut trytes);
+    Ok(trytes)
+}
+
+/// Decodes `num_trytes` trytes, writing them into `trytes`.
+///
+/// Returns the number of trytes written.
+pub fn to_trytes_into(
+    packed: &[u8],
+    num_trytes: usize,
+    trytes: &mut [Tryte],
+) -> Result<usize, ConversionError> {
+    T5B1::check_packed(packed, num_trytes * 3)?;
+    check_buf_len(trytes.len(), num_trytes)?;
+
+    write_trytes(packed, &mut trytes[..num_trytes]);
+    Ok(num_trytes)
+}
+
+#[inline]
+fn write_trytes(packed: &[u8], trytes: &mut [Tryte]) {
+    let mut trits = unpack(packed);
+    for tryte in trytes.iter_mut() {
+        *tryte = from_trit_triplet(take_trits(&mut trits));
+    }
+}
+
+/// Decodes `num_bytes` 9/2 encoded bytes.
+///
+/// This function will panic if `packed` doesn't hold exactly `num_bytes / 2 * 9` trits.
+#[cfg(feature = "alloc")]
+pub fn to_bytes(packed: &[u8], num_bytes: usize) -> Vec<u8> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_len_multiple_of(num_bytes, 2).unwrap();
+        T5B1::check_packed(packed, num_bytes / 2 * 9).unwrap();
+    }
+
+    let mut bytes = vec![0; num_bytes];
+    write_bytes(packed, &mut bytes);
+    bytes
+}
+
+/// Tries to decode `num_bytes` 9/2 encoded bytes.
+#[cfg(feature = "alloc")]
+pub fn try_to_bytes(packed: &[u8], num_bytes: usize) -> Result<Vec<u8>, ConversionError> {
+    check_len_multiple_of(num_bytes, 2)?;
+    T5B1::check_packed(packed, num_bytes / 2 * 9)?;
+
+    let mut bytes = vec![0; num_bytes];
+    write_bytes(packed, &mut bytes);
+    Ok(bytes)
+}
+
+/// Decodes `num_bytes` 9/2 encoded bytes, writing them into `bytes`.
+///
+/// Returns the number of bytes written.
+pub fn to_bytes_into(
+    packed: &[u8],
+    num_bytes: usize,
+    bytes: &mut [u8],
+) -> Result<usize, ConversionError> {
+    check_len_multiple_of(num_bytes, 2)?;
+    T5B1::check_packed(packed, num_bytes / 2 * 9)?;
+    check_buf_len(bytes.len(), num_bytes)?;
+
+    write_bytes(packed, &mut bytes[..num_bytes]);
+    Ok(num_bytes)
+}
+
+#[inline]
+fn write_bytes(packed: &[u8], bytes: &mut [u8]) {
+    let mut trits = unpack(packed);
+    for pair in bytes.chunks_exact_mut(2) {
+        let nine_trits: [Trit; 9] = take_trits(&mut trits);
+        crate::bytes::write_from_trits(&nine_trits, pair);
+    }
+}
+
+#[inline]
+fn pack<I: Iterator<Item = Trit>>(trits: I, packed: &mut [u8]) {
+    pack_balanced::<I, TRITS_PER_BYTE>(trits, packed);
+}
+
+#[inline]
+fn unpack_into(packed: &[u8], trits: &mut [Trit]) {
+    trits.iter_mut().zip(unpack(packed)).for_each(|(t, u)| *t = u);
+}
+
+#[inline]
+fn unpack(packed: &[u8]) -> impl Iterator<Item = Trit> + '_ {
+    unpack_balanced::<TRITS_PER_BYTE>(packed)
+}
+
+#[inline]
+fn unpack_9_2(bytes: &[u8]) -> impl Iterator<Item = Trit> + '_ {
+    bytes.chunks_exact(2).flat_map(|pair| {
+        let mut trits = [Trit::Zero; 9];
+        crate::trits::write_from_bytes(pair, &mut trits);
+        trits
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use rand::{thread_rng, Rng};
+
+    fn random_trits(len: usize) -> Vec<Trit> {
+        let raw: Vec<i8> = (0..len).map(|_| thread_rng().gen_range(-1, 2)).collect();
+        crate::trits::from_raw(&raw)
+    }
+
+    #[test]
+    fn test_from_trits() {
+        assert_eq!(vec![121], from_trits(&[Trit::Pos; 5]));
+        assert_eq!(vec![135], from_trits(&[Trit::Neg; 5]));
+        assert_eq!(vec![0], from_trits(&[Trit::Zero; 5]));
+        assert_eq!(vec![7, 1], from_trits(&crate::trits::from_raw(&[1, -1, 1, 0, 0, 1])));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in 0..30 {
+            let trits = random_trits(len);
+            assert_eq!(trits, to_trits(&from_trits(&trits), len));
+        }
+
+        let trits = random_trits(TRANSACTION_SIZE_TRITS);
+        assert_eq!(&trits[..], &to_trits_all(&from_trits_all(&trits))[..]);
+    }
+
+    #[test]
+    fn test_against_9_2_bytes() {
+        let trits = random_trits(SIG_MSG_FRG_SIZE_TRITS);
+        let bytes = crate::bytes::from_trits(&trits);
+        let trytes = crate::trytes::from_trits(&trits);
+
+        assert_eq!(from_trits(&trits), from_bytes(&bytes));
+        assert_eq!(from_trits(&trits), from_trytes(&trytes));
+
+        let packed = from_trits(&trits);
+        assert_eq!(bytes, to_bytes(&packed, bytes.len()));
+        assert_eq!(trytes, to_trytes(&packed, trytes.len()));
+    }
+
+    #[test]
+    fn test_into() {
+        let trits = random_trits(12);
+        let mut packed = [0; 4];
+        assert_eq!(Ok(3), from_trits_into(&trits, &mut packed));
+
+        let mut decoded = [Trit::Zero; 12];
+        assert_eq!(Ok(12), to_trits_into(&packed[..3], 12, &mut decoded));
+        assert_eq!(trits, decoded);
+
+        let err = from_trits_into(&trits, &mut packed[..2]).unwrap_err();
+        assert_eq!(ErrorKind::BufferTooSmall, err.kind());
+        assert_eq!(3, err.index());
+    }
+
+    #[test]
+    fn test_try_to_trits() {
+        let err = try_to_trits(&[0, 122], 10).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(1, err.index());
+
+        let err = try_to_trits(&[0, 0], 11).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+
+        // The 2 trits padding the last byte aren't zero.
+        let err = try_to_trits(&[0, 0, 27], 13).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(2, err.index());
+        assert!(try_to_trits(&[0, 0, 13], 13).is_ok());
+
+        let err = try_to_bytes(&[0, 81], 2).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(1, err.index());
+
+        assert_eq!(ErrorKind::InvalidLength, try_to_trits_243(&[0; 48]).unwrap_err().kind());
+    }
+}
@@ -0,0 +1,641 @@
+//! Transactions and converter functions from their 2673 tryte encoding.
+//!
+//! A transaction can be parsed from trytes, a tryte string, 8019 trits or the 1782 bytes of the
+//! 9/2 encoding returned by `bytes::from_trytes_all`. The numeric fields are decoded with
+//! `numbers::from_trytes_max27` and `numbers::from_trytes_max11`, all other fields are kept as
+//! trytes.
+//!
+//! The inverse, serializing a transaction, is done by `to_trytes`, `to_trits` and `to_bytes`, and
+//! `TransactionBuilder` builds transactions field by field.
+
+use crate::{
+    bytes,
+    constants::SIG_MSG_FRG_SIZE_TRYTES,
+    constants::TRANSACTION_SIZE_BYTES,
+    constants::TRANSACTION_SIZE_TRITS,
+    constants::TRANSACTION_SIZE_TRYTES,
+    error::ConversionError,
+    error::ErrorKind,
+    numbers,
+    trits,
+    trytes,
+    types::Byte,
+    types::Trit,
+    types::Tryte,
+    types::S129,
+    util::as_array,
+    util::check_len,
+    util::check_max_len,
+};
+
+const SIGNATURE_OR_MESSAGE_OFFSET: usize = 0;
+const ADDRESS_OFFSET: usize = SIGNATURE_OR_MESSAGE_OFFSET + SIG_MSG_FRG_SIZE_TRYTES;
+const VALUE_OFFSET: usize = ADDRESS_OFFSET + 81;
+const OBSOLETE_TAG_OFFSET: usize = VALUE_OFFSET + 27;
+const TIMESTAMP_OFFSET: usize = OBSOLETE_TAG_OFFSET + 27;
+const CURRENT_INDEX_OFFSET: usize = TIMESTAMP_OFFSET + 9;
+const LAST_INDEX_OFFSET: usize = CURRENT_INDEX_OFFSET + 9;
+const BUNDLE_OFFSET: usize = LAST_INDEX_OFFSET + 9;
+const TRUNK_OFFSET: usize = BUNDLE_OFFSET + 81;
+const BRANCH_OFFSET: usize = TRUNK_OFFSET + 81;
+const TAG_OFFSET: usize = BRANCH_OFFSET + 81;
+const ATTACHMENT_TIMESTAMP_OFFSET: usize = TAG_OFFSET + 27;
+const ATTACHMENT_TIMESTAMP_LOWER_BOUND_OFFSET: usize = ATTACHMENT_TIMESTAMP_OFFSET + 9;
+const ATTACHMENT_TIMESTAMP_UPPER_BOUND_OFFSET: usize = ATTACHMENT_TIMESTAMP_LOWER_BOUND_OFFSET + 9;
+const NONCE_OFFSET: usize = ATTACHMENT_TIMESTAMP_UPPER_BOUND_OFFSET + 9;
+
+/// A transaction with its numeric fields decoded.
+// At 2.6kB a transaction is too big to be copied implicitly.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transaction {
+    /// The signature fragment or message, 2187 trytes.
+    pub signature_or_message: [Tryte; SIG_MSG_FRG_SIZE_TRYTES],
+    /// The address, 81 trytes.
+    pub address: [Tryte; 81],
+    /// The value, 27 trytes.
+    pub value: S129,
+    /// The obsolete tag, 27 trytes.
+    pub obsolete_tag: [Tryte; 27],
+    /// The timestamp, 9 trytes.
+    pub timestamp: i64,
+    /// The index within the bundle, 9 trytes.
+    pub current_index: i64,
+    /// The index of the last transaction of the bundle, 9 trytes.
+    pub last_index: i64,
+    /// The bundle hash, 81 trytes.
+    pub bundle: [Tryte; 81],
+    /// The trunk transaction hash, 81 trytes.
+    pub trunk: [Tryte; 81],
+    /// The branch transaction hash, 81 trytes.
+    pub branch: [Tryte; 81],
+    /// The tag, 27 trytes.
+    pub tag: [Tryte; 27],
+    /// The attachment timestamp, 9 trytes.
+    pub attachment_timestamp: i64,
+    /// The lower bound of the attachment timestamp, 9 trytes.
+    pub attachment_timestamp_lower_bound: i64,
+    /// The upper bound of the attachment timestamp, 9 trytes.
+    pub attachment_timestamp_upper_bound: i64,
+    /// The nonce, 27 trytes.
+    pub nonce: [Tryte; 27],
+}
+
+impl Default for Transaction {
+    /// Returns a transaction whose tryte fields are all `9` and whose numeric fields are zero.
+    fn default() -> Self {
+        Transaction {
+            signature_or_message: [Tryte::default(); SIG_MSG_FRG_SIZE_TRYTES],
+            address: [Tryte::default(); 81],
+            value: S129::ZERO,
+            obsolete_tag: [Tryte::default(); 27],
+            timestamp: 0,
+            current_index: 0,
+            last_index: 0,
+            bundle: [Tryte::default(); 81],
+            trunk: [Tryte::default(); 81],
+            branch: [Tryte::default(); 81],
+            tag: [Tryte::default(); 27],
+            attachment_timestamp: 0,
+            attachment_timestamp_lower_bound: 0,
+            attachment_timestamp_upper_bound: 0,
+            nonce: [Tryte::default(); 27],
+        }
+    }
+}
+
+/// Parses a transaction from 2673 trytes.
+pub fn from_trytes_array(trytes: &[Tryte; TRANSACTION_SIZE_TRYTES]) -> Transaction {
+    Transaction {
+        signature_or_message: field(trytes, SIGNATURE_OR_MESSAGE_OFFSET),
+        address: field(trytes, ADDRESS_OFFSET),
+        value: numbers::from_trytes_max27(&trytes[VALUE_OFFSET..OBSOLETE_TAG_OFFSET]),
+        obsolete_tag: field(trytes, OBSOLETE_TAG_OFFSET),
+        timestamp: number_field(trytes, TIMESTAMP_OFFSET),
+        current_index: number_field(trytes, CURRENT_INDEX_OFFSET),
+        last_index: number_field(trytes, LAST_INDEX_OFFSET),
+        bundle: field(trytes, BUNDLE_OFFSET),
+        trunk: field(trytes, TRUNK_OFFSET),
+        branch: field(trytes, BRANCH_OFFSET),
+        tag: field(trytes, TAG_OFFSET),
+        attachment_timestamp: number_field(trytes, ATTACHMENT_TIMESTAMP_OFFSET),
+        attachment_timestamp_lower_bound: number_field(
+            trytes,
+            ATTACHMENT_TIMESTAMP_LOWER_BOUND_OFFSET,
+        ),
+        attachment_timestamp_upper_bound: number_field(
+            trytes,
+            ATTACHMENT_TIMESTAMP_UPPER_BOUND_OFFSET,
+        ),
+        nonce: field(trytes, NONCE_OFFSET),
+    }
+}
+
+/// Parses a transaction from a slice of trytes.
+///
+/// This function will panic if the slice is not 2673 trytes long.
+pub fn from_trytes(trytes: &[Tryte]) -> Transaction {
+    try_from_trytes(trytes).unwrap()
+}
+
+/// Tries to parse a transaction from a slice of trytes.
+///
+/// Fails if the slice is not 2673 trytes long.
+pub fn try_from_trytes(trytes: &[Tryte]) -> Result<Transaction, ConversionError> {
+    Ok(from_trytes_array(as_array(trytes)?))
+}
+
+/// Parses a transaction from a tryte string.
+///
+/// This function will panic if the string is not 2673 trytes long.
+pub fn from_tryte_str(tryte_str: &str) -> Transaction {
+    try_from_tryte_str(tryte_str).unwrap()
+}
+
+/// Tries to parse a transaction from a tryte string.
+///
+/// Fails if the string is not 2673 trytes long or contains characters other than trytes.
+pub fn try_from_tryte_str(tryte_str: &str) -> Result<Transaction, ConversionError> {
+    check_len(tryte_str.len(), TRANSACTION_SIZE_TRYTES)?;
+
+    let mut trytes = [Tryte::default(); TRANSACTION_SIZE_TRYTES];
+    trytes::from_tryte_str_into(tryte_str, &mut trytes)?;
+    Ok(from_trytes_array(&trytes))
+}
+
+/// Parses a transaction from a slice of trits.
+///
+/// This function will panic if the slice is not 8019 trits long.
+pub fn from_trits(trits: &[Trit]) -> Transaction {
+    from_trytes_array(&trytes::from_trits_all(trits))
+}
+
+/// Tries to parse a transaction from a slice of trits.
+///
+/// Fails if the slice is not 8019 trits long.
+pub fn try_from_trits(trits: &[Trit]) -> Result<Transaction, ConversionError> {
+    Ok(from_trytes_array(&trytes::try_from_trits_all(trits)?))
+}
+
+/// Parses a transaction from a slice of bytes in the 9/2 encoding.
+///
+/// This function will panic if the slice is not 1782 bytes long or contains invalid bytes.
+pub fn from_bytes(bytes: &[u8]) -> Transaction {
+    from_trytes_array(&trytes::from_bytes_all(bytes))
+}
+
+/// Tries to parse a transaction from a slice of bytes in the 9/2 encoding.
+///
+/// Fails if the slice is not 1782 bytes long or contains invalid bytes.
+pub fn try_from_bytes(bytes: &[u8]) -> Result<Transaction, ConversionError> {
+    Ok(from_trytes_array(&trytes::try_from_bytes_all(bytes)?))
+}
+
+/// Serializes a transaction to 2673 trytes.
+///
+/// This function will panic if a numeric field is out of the range of its trytes.
+pub fn to_trytes(transaction: &Transaction) -> [Tryte; TRANSACTION_SIZE_TRYTES] {
+    try_to_trytes(transaction).unwrap()
+}
+
+/// Tries to serialize a transaction to 2673 trytes.
+///
+/// Fails if a numeric field is out of the range of its trytes, the value of 27 trytes and all
+/// others of 9 trytes. The index of the error is the tryte offset of the field.
+pub fn try_to_trytes(
+    transaction: &Transaction,
+) -> Result<[Tryte; TRANSACTION_SIZE_TRYTES], ConversionError> {
+    let mut trytes = [Tryte::default(); TRANSACTION_SIZE_TRYTES];
+
+    let value = trytes::try_from_s129_to_27(transaction.value)
+        .map_err(|_| ConversionError::new(ErrorKind::OutOfRange, VALUE_OFFSET))?;
+
+    write_field(&mut trytes, SIGNATURE_OR_MESSAGE_OFFSET, &transaction.signature_or_message);
+    write_field(&mut trytes, ADDRESS_OFFSET, &transaction.address);
+    write_field(&mut trytes, VALUE_OFFSET, &value);
+    write_field(&mut trytes, OBSOLETE_TAG_OFFSET, &transaction.obsolete_tag);
+    write_number_field(&mut trytes, TIMESTAMP_OFFSET, transaction.timestamp)?;
+    write_number_field(&mut trytes, CURRENT_INDEX_OFFSET, transaction.current_index)?;
+    write_number_field(&mut trytes, LAST_INDEX_OFFSET, transaction.last_index)?;
+    write_field(&mut trytes, BUNDLE_OFFSET, &transaction.bundle);
+    write_field(&mut trytes, TRUNK_OFFSET, &transaction.trunk);
+    write_field(&mut trytes, BRANCH_OFFSET, &transaction.branch);
+    write_field(&mut trytes, TAG_OFFSET, &transaction.tag);
+    write_number_field(&mut trytes, ATTACHMENT_TIMESTAMP_OFFSET, transaction.attachment_timestamp)?;
+    write_number_field(
+        &mut trytes,
+        ATTACHMENT_TIMESTAMP_LOWER_BOUND_OFFSET,
+        transaction.attachment_timestamp_lower_bound,
+    )?;
+    write_number_field(
+        &mut trytes,
+        ATTACHMENT_TIMESTAMP_UPPER_BOUND_OFFSET,
+        transaction.attachment_timestamp_upper_bound,
+    )?;
+    write_field(&mut trytes, NONCE_OFFSET, &transaction.nonce);
+
+    Ok(trytes)
+}
+
+/// Serializes a transaction to 8019 trits.
+///
+/// This function will panic if a numeric field is out of the range of its trytes.
+pub fn to_trits(transaction: &Transaction) -> [Trit; TRANSACTION_SIZE_TRITS] {
+    trits::from_trytes_all(&to_trytes(transaction))
+}
+
+/// Tries to serialize a transaction to 8019 trits.
+///
+/// Fails like `try_to_trytes`.
+pub fn try_to_trits(
+    transaction: &Transaction,
+) -> Result<[Trit; TRANSACTION_SIZE_TRITS], ConversionError> {
+    Ok(trits::from_trytes_all(&try_to_trytes(transaction)?))
+}
+
+/// Serializes a transaction to 1782 bytes in the 9/2 encoding.
+///
+/// This function will panic if a numeric field is out of the range of its trytes.
+pub fn to_bytes(transaction: &Transaction) -> [Byte; TRANSACTION_SIZE_BYTES] {
+    bytes::from_trytes_all(&to_trytes(transaction))
+}
+
+/// Tries to serialize a transaction to 1782 bytes in the 9/2 encoding.
+///
+/// Fails like `try_to_trytes`.
+pub fn try_to_bytes(
+    transaction: &Transaction,
+) -> Result<[Byte; TRANSACTION_SIZE_BYTES], ConversionError> {
+    Ok(bytes::from_trytes_all(&try_to_trytes(transaction)?))
+}
+
+macro_rules! trytes_setter {
+    ($name:ident, $offset:expr, $check:ident, $doc:expr) => {
+        #[doc = $doc]
+        pub fn $name(mut self, trytes: &[Tryte]) -> Self {
+            if let Some(field) = self.check($offset, $check(trytes)) {
+                self.transaction.$name = field;
+            }
+            self
+        }
+    };
+}
+
+macro_rules! number_setter {
+    ($name:ident, $offset:expr, $doc:expr) => {
+        #[doc = $doc]
+        pub fn $name(mut self, number: i64) -> Self {
+            if let Some(number) = self.check($offset, check_number_field(number)) {
+                self.transaction.$name = number;
+            }
+            self
+        }
+    };
+}
+
+/// A builder that validates every field of a transaction.
+///
+/// Fields that are not set are all `9` trytes or zero, see `Transaction::default`. Tryte fields
+/// that may be shorter are padded with `9`s on the right. Setting an invalid field doesn't panic,
+/// instead the first invalid field is reported by `build`, with the tryte offset of the field as
+/// the index of the error.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionBuilder {
+    transaction: Transaction,
+    error: Option<ConversionError>,
+}
+
+impl TransactionBuilder {
+    /// Creates a builder of a transaction whose fields are all `9` trytes or zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    trytes_setter!(
+        signature_or_message,
+        SIGNATURE_OR_MESSAGE_OFFSET,
+        check_padded_field,
+        "Sets the signature fragment or message of up to 2187 trytes."
+    );
+    trytes_setter!(address, ADDRESS_OFFSET, check_field, "Sets the address of 81 trytes.");
+
+    /// Sets the value, which has to be within the token supply of 11 trytes, `±(3^33-1)/2`.
+    pub fn value(mut self, value: i64) -> Self {
+        let value =
+            trytes::try_from_num_i64_to_11(value).map(|_| S129::from(value)).map_err(|e| e.kind());
+        if let Some(value) = self.check(VALUE_OFFSET, value) {
+            self.transaction.value = value;
+        }
+        self
+    }
+
+    trytes_setter!(
+        obsolete_tag,
+        OBSOLETE_TAG_OFFSET,
+        check_padded_field,
+        "Sets the obsolete tag of up to 27 trytes."
+    );
+    number_setter!(
+        timestamp,
+        TIMESTAMP_OFFSET,
+        "Sets the timestamp, which has to be within 27 trits, `±(3^27-1)/2`."
+    );
+    number_setter!(
+        current_index,
+        CURRENT_INDEX_OFFSET,
+        "Sets the index within the bundle, which has to be within 27 trits."
+    );
+    number_setter!(
+        last_index,
+        LAST_INDEX_OFFSET,
+        "Sets the index of the last transaction of the bundle, which has to be within 27 trits."
+    );
+    trytes_setter!(bundle, BUNDLE_OFFSET, check_field, "Sets the bundle hash of 81 trytes.");
+    trytes_setter!(
+        trunk,
+        TRUNK_OFFSET,
+        check_field,
+        "Sets the trunk transaction hash of 81 trytes."
+    );
+    trytes_setter!(
+        branch,
+        BRANCH_OFFSET,
+        check_field,
+        "Sets the branch transaction hash of 81 trytes."
+    );
+    trytes_setter!(tag, TAG_OFFSET, check_padded_field, "Sets the tag of up to 27 trytes.");
+    number_setter!(
+        attachment_timestamp,
+        ATTACHMENT_TIMESTAMP_OFFSET,
+        "Sets the attachment timestamp, which has to be within 27 trits."
+    );
+    number_setter!(
+        attachment_timestamp_lower_bound,
+        ATTACHMENT_TIMESTAMP_LOWER_BOUND_OFFSET,
+        "Sets the lower bound of the attachment timestamp, which has to be within 27 trits."
+    );
+    number_setter!(
+        attachment_timestamp_upper_bound,
+        ATTACHMENT_TIMESTAMP_UPPER_BOUND_OFFSET,
+        "Sets the upper bound of the attachment timestamp, which has to be within 27 trits."
+    );
+    trytes_setter!(nonce, NONCE_OFFSET, check_field, "Sets the nonce of 27 trytes.");
+
+    /// Builds the transaction.
+    ///
+    /// Fails if a field was invalid.
+    pub fn build(self) -> Result<Transaction, ConversionError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.transaction),
+        }
+    }
+
+    /// Builds the transaction and serializes it to 2673 trytes.
+    pub fn build_trytes(self) -> Result<[Tryte; TRANSACTION_SIZE_TRYTES], ConversionError> {
+        // The setters ensure that every numeric field is in range.
+        Ok(to_trytes(&self.build()?))
+    }
+
+    /// Builds the transaction and serializes it to 8019 trits.
+    pub fn build_trits(self) -> Result<[Trit; TRANSACTION_SIZE_TRITS], ConversionError> {
+        Ok(to_trits(&self.build()?))
+    }
+
+    /// Builds the transaction and serializes it to 1782 bytes in the 9/2 encoding.
+    pub fn build_bytes(self) -> Result<[Byte; TRANSACTION_SIZE_BYTES], ConversionError> {
+        Ok(to_bytes(&self.build()?))
+    }
+
+    /// Keeps the first error, so `build` reports the first invalid field.
+    fn check<T>(&mut self, offset: usize, field: Result<T, ErrorKind>) -> Option<T> {
+        match field {
+            Ok(field) => Some(field),
+            Err(kind) => {
+                self.error.get_or_insert(ConversionError::new(kind, offset));
+                None
+            }
+        }
+    }
+}
+
+fn check_field<const N: usize>(trytes: &[Tryte]) -> Result<[Tryte; N], ErrorKind> {
+    as_array(trytes).copied().map_err(|e| e.kind())
+}
+
+fn check_padded_field<const N: usize>(trytes: &[Tryte]) -> Result<[Tryte; N], ErrorKind> {
+    check_max_len(trytes.len(), N).map_err(|e| e.kind())?;
+
+    let mut field = [Tryte::default(); N];
+    field[..trytes.len()].copy_from_slice(trytes);
+    Ok(field)
+}
+
+fn check_number_field(number: i64) -> Result<i64, ErrorKind> {
+    trytes::try_from_num_i64_array::<9>(number).map(|_| number).map_err(|e| e.kind())
+}
+
+#[inline]
+fn write_field(trytes: &mut [Tryte; TRANSACTION_SIZE_TRYTES], offset: usize, field: &[Tryte]) {
+    trytes[offset..offset + field.len()].copy_from_slice(field);
+}
+
+#[inline]
+fn write_number_field(
+    trytes: &mut [Tryte; TRANSACTION_SIZE_TRYTES],
+    offset: usize,
+    number: i64,
+) -> Result<(), ConversionError> {
+    let field = trytes::try_from_num_i64_array::<9>(number)
+        .map_err(|_| ConversionError::new(ErrorKind::OutOfRange, offset))?;
+    write_field(trytes, offset, &field);
+    Ok(())
+}
+
+#[inline]
+fn field<const N: usize>(trytes: &[Tryte; TRANSACTION_SIZE_TRYTES], offset: usize) -> [Tryte; N] {
+    let mut field = [Tryte::default(); N];
+    field.copy_from_slice(&trytes[offset..offset + N]);
+    field
+}
+
+#[inline]
+fn number_field(trytes: &[Tryte; TRANSACTION_SIZE_TRYTES], offset: usize) -> i64 {
+    numbers::from_trytes_max11(&trytes[offset..offset + 9])
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::string::String;
+
+    use super::*;
+    use crate::{test_vectors::TRANSACTION, types::Sign};
+
+    fn to_str(trytes: &[Tryte]) -> String {
+        trytes.iter().map(|&t| char::from(t)).collect()
+    }
+
+    #[test]
+    fn test_from_tryte_str() {
+        let tx = from_tryte_str(TRANSACTION);
+
+        assert_eq!(&TRANSACTION[..2187], to_str(&tx.signature_or_message));
+        assert_eq!(
+            "MXKZAGDGKVADXOVCAXEQYZGOGQKDLKIUPYXIL9PXYBQXGYDEGNXTFURSWQYLJDFKEV9VVBBQLTLHIBTFY",
+            to_str(&tx.address)
+        );
+        assert_eq!(S129(Sign::Neg, 30417276522028963598351422436046789277), tx.value);
+        assert_eq!("SQPIBL9LYZHQKKOVF9TFVTTXQEU", to_str(&tx.obsolete_tag));
+        assert_eq!(-2024552820469, tx.timestamp);
+        assert_eq!(1179577940848, tx.current_index);
+        assert_eq!(1209567866460, tx.last_index);
+        assert_eq!(
+            "QQSQLDHZ9999999999999999999TRINITY99999999999999999999TNXSQ9D99A99999999B99999999",
+            to_str(&tx.bundle)
+        );
+        assert_eq!(
+            "OGBHPUUHS9CKWSAPIMDIRNSUJ9CFPGKTUFAGQYVMFKOZSVAHIFJXWCFBZLICUWF9GNDZWCOWDUIIZ9999",
+            to_str(&tx.trunk)
+        );
+        assert_eq!(
+            "OXNRVXLBKJXEZMVABR9UQBVSTBDFSAJVRRNFEJRL9UFTOFPJHQMQKAJHDBIQAETS9OUVTQ9DSPAOZ9999",
+            to_str(&tx.branch)
+        );
+        assert_eq!("TRINITY99999999999999999999", to_str(&tx.tag));
+        assert_eq!(1544207541879, tx.attachment_timestamp);
+        assert_eq!(0, tx.attachment_timestamp_lower_bound);
+        assert_eq!(3812798742493, tx.attachment_timestamp_upper_bound);
+        assert_eq!("DTIZE9999999999999999999999", to_str(&tx.nonce));
+
+        let err = try_from_tryte_str(&TRANSACTION[1..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(2672, err.index());
+
+        let err = try_from_tryte_str(&TRANSACTION.replacen('M', "m", 1)).unwrap_err();
+        assert_eq!(ErrorKind::InvalidTryte, err.kind());
+    }
+
+    #[test]
+    fn test_from_trytes_trits_bytes() {
+        let tx = from_tryte_str(TRANSACTION);
+        let trytes = trytes::from_tryte_str(TRANSACTION);
+
+        assert_eq!(tx, from_trytes(&trytes));
+        assert_eq!(tx, from_trits(&trits::from_trytes(&trytes)));
+        assert_eq!(tx, from_bytes(&bytes::from_trytes_all(&trytes)));
+
+        assert_eq!(ErrorKind::InvalidLength, try_from_trytes(&trytes[1..]).unwrap_err().kind());
+        assert_eq!(
+            ErrorKind::InvalidLength,
+            try_from_trits(&trits::from_trytes(&trytes[1..])).unwrap_err().kind()
+        );
+
+        let mut bytes = bytes::from_trytes_all(&trytes);
+        assert_eq!(ErrorKind::InvalidLength, try_from_bytes(&bytes[1..]).unwrap_err().kind());
+        bytes[0] = 255;
+        assert_eq!(ErrorKind::OutOfRange, try_from_bytes(&bytes).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_to_trytes_trits_bytes() {
+        let tx = from_tryte_str(TRANSACTION);
+        let trytes = trytes::from_tryte_str(TRANSACTION);
+
+        assert_eq!(&trytes[..], &to_trytes(&tx)[..]);
+        assert_eq!(&trits::from_trytes(&trytes)[..], &to_trits(&tx)[..]);
+        assert_eq!(&bytes::from_trytes(&trytes)[..], &to_bytes(&tx)[..]);
+
+        let mut invalid = tx.clone();
+        invalid.attachment_timestamp_upper_bound = 3812798742494;
+        let err = try_to_trytes(&invalid).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(2637, err.index());
+
+        invalid.value = S129::MAX;
+        assert_eq!(2268, try_to_bytes(&invalid).unwrap_err().index());
+    }
+
+    #[test]
+    fn test_builder() {
+        let tx = from_tryte_str(TRANSACTION);
+
+        let built = TransactionBuilder::new()
+            .signature_or_message(&tx.signature_or_message)
+            .address(&tx.address)
+            .value(-2779530283277761)
+            .obsolete_tag(&tx.obsolete_tag[..8])
+            .timestamp(tx.timestamp)
+            .current_index(tx.current_index)
+            .last_index(tx.last_index)
+            .bundle(&tx.bundle)
+            .trunk(&tx.trunk)
+            .branch(&tx.branch)
+            .tag(&tx.tag[..7])
+            .attachment_timestamp(tx.attachment_timestamp)
+            .attachment_timestamp_lower_bound(tx.attachment_timestamp_lower_bound)
+            .attachment_timestamp_upper_bound(tx.attachment_timestamp_upper_bound)
+            .nonce(&tx.nonce)
+            .build_trytes()
+            .unwrap();
+
+        let expected = format!(
+            "{}NNNNNNNNNNN9999999999999999SQPIBL9L9999999999999999999{}",
+            &TRANSACTION[..2268],
+            &TRANSACTION[2322..]
+        );
+        assert_eq!(expected, to_str(&built));
+        assert_eq!(
+            from_trytes(&built),
+            TransactionBuilder::new()
+                .signature_or_message(&tx.signature_or_message)
+                .address(&tx.address)
+                .value(-2779530283277761)
+                .obsolete_tag(&tx.obsolete_tag[..8])
+                .timestamp(tx.timestamp)
+                .current_index(tx.current_index)
+                .last_index(tx.last_index)
+                .bundle(&tx.bundle)
+                .trunk(&tx.trunk)
+                .branch(&tx.branch)
+                .tag(&tx.tag)
+                .attachment_timestamp(tx.attachment_timestamp)
+                .attachment_timestamp_lower_bound(tx.attachment_timestamp_lower_bound)
+                .attachment_timestamp_upper_bound(tx.attachment_timestamp_upper_bound)
+                .nonce(&tx.nonce)
+                .build()
+                .unwrap()
+        );
+        assert_eq!(Transaction::default(), TransactionBuilder::new().build().unwrap());
+        assert_eq!(
+            &to_bytes(&Transaction::default())[..],
+            &TransactionBuilder::new().build_bytes().unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        let tx = from_tryte_str(TRANSACTION);
+
+        let err = TransactionBuilder::new().address(&tx.address[1..]).build().unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(2187, err.index());
+
+        let err = TransactionBuilder::new().tag(&tx.bundle).build_trits().unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(2592, err.index());
+
+        let err = TransactionBuilder::new().value(2779530283277762).build().unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(2268, err.index());
+
+        // The first invalid field is reported.
+        let err = TransactionBuilder::new()
+            .timestamp(-3812798742494)
+            .nonce(&tx.bundle)
+            .build_bytes()
+            .unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(2322, err.index());
+    }
+}
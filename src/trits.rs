@@ -1,67 +1,137 @@
 //! Converter functions that convert to various datatypes to Trits.
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::vec::Vec;
 
 use crate::{
+    constants::MAX_S129_TRITS_ABS,
     constants::SIG_MSG_FRG_SIZE_TRITS,
     constants::TRANSACTION_SIZE_TRITS,
+    constants::TRIT_LENGTH_FOR_MAX_S129,
+    error::ConversionError,
+    error::ErrorKind,
+    luts::ASCII_CODE_OFFSET,
     luts::ASCII_CODE_TO_TRITS,
-    luts::ASCII_CODE_TO_TRYTE_CODE,
     luts::TRYTE_CODE_TO_TRITS,
+    simd,
     types::Trit,
     types::Tryte,
+    types::S129,
+    util::as_array,
+    util::assert_len_ratio,
+    util::check_buf_len,
+    util::check_bytes,
+    util::check_len,
+    util::check_len_multiple_of,
+    util::check_trits,
+    util::check_tryte_str,
+    util::into_heapless,
 };
 
-macro_rules! from_bytes_conv {
-    ($func_name:ident, $length:expr) => {
-        /// Converts fixed-sized slices of bytes to trits.
-        pub fn $func_name(bytes: &[u8]) -> [Trit; $length] {
-            #[cfg(not(feature = "no_checks"))]
-            {
-                assert_eq!(0, bytes.len() % 2);
-                assert_eq!($length, bytes.len() / 2 * 9);
-            }
+/// Converts an array of bytes to an array of trits.
+///
+/// `M` has to be `N / 2 * 9`, which is checked at compile time.
+pub fn from_bytes_array<const N: usize, const M: usize>(bytes: &[u8; N]) -> [Trit; M] {
+    assert_len_ratio::<N, M, 9, 2>();
 
-            let mut trits = [0_i8; $length];
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_bytes(bytes).unwrap();
+    }
 
-            for i in 0..$length / 9 {
-                let b0 = bytes[2 * i] as usize;
-                let b1 = bytes[2 * i + 1] as usize;
+    let mut trits = [Trit::Zero; M];
+    write_from_bytes(bytes, &mut trits);
+    trits
+}
 
-                let offset = i * 9;
+/// Tries to convert an array of bytes to an array of trits.
+///
+/// `M` has to be `N / 2 * 9`, which is checked at compile time.
+pub fn try_from_bytes_array<const N: usize, const M: usize>(
+    bytes: &[u8; N],
+) -> Result<[Trit; M], ConversionError> {
+    assert_len_ratio::<N, M, 9, 2>();
 
-                trits[offset..offset + 3].copy_from_slice(&TRYTE_CODE_TO_TRITS[b0 / 8][..]);
-                trits[(offset + 3)..(offset + 6)].copy_from_slice(&TRYTE_CODE_TO_TRITS[b1 / 8]);
-                trits[(offset + 6)..(offset + 9)]
-                    .copy_from_slice(&TRYTE_CODE_TO_TRITS[b0 % 8 + 8 * (b1 % 8)]);
-            }
+    check_bytes(bytes)?;
 
-            trits
+    let mut trits = [Trit::Zero; M];
+    write_from_bytes(bytes, &mut trits);
+    Ok(trits)
+}
+
+macro_rules! from_bytes_conv {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
+        /// Converts fixed-sized slices of bytes to trits.
+        pub fn $func_name(bytes: &[u8]) -> [Trit; $length] {
+            from_bytes_array::<{ $length / 9 * 2 }, $length>(as_array(bytes).unwrap())
+        }
+
+        /// Tries to convert fixed-sized slices of bytes to trits.
+        pub fn $try_func_name(bytes: &[u8]) -> Result<[Trit; $length], ConversionError> {
+            try_from_bytes_array::<{ $length / 9 * 2 }, $length>(as_array(bytes)?)
         }
     };
 }
 
-from_bytes_conv!(from_bytes_all, TRANSACTION_SIZE_TRITS);
-from_bytes_conv!(from_bytes_sig, SIG_MSG_FRG_SIZE_TRITS);
-from_bytes_conv!(from_bytes_54, 243);
-from_bytes_conv!(from_bytes_18, 81);
-from_bytes_conv!(from_bytes_6, 27);
+from_bytes_conv!(from_bytes_all, try_from_bytes_all, TRANSACTION_SIZE_TRITS);
+from_bytes_conv!(from_bytes_sig, try_from_bytes_sig, SIG_MSG_FRG_SIZE_TRITS);
+from_bytes_conv!(from_bytes_54, try_from_bytes_54, 243);
+from_bytes_conv!(from_bytes_18, try_from_bytes_18, 81);
+from_bytes_conv!(from_bytes_6, try_from_bytes_6, 27);
 
 /// Converts arbitrary slices of bytes to trits.
+#[cfg(feature = "alloc")]
 pub fn from_bytes(bytes: &[u8]) -> Vec<Trit> {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert_eq!(0, bytes.len() % 2);
+        check_len_multiple_of(bytes.len(), 2).unwrap();
+        check_bytes(bytes).unwrap();
     }
 
-    let mut trits = vec![0_i8; bytes.len() / 2 * 9];
+    let mut trits = vec![Trit::Zero; bytes.len() / 2 * 9];
+    write_from_bytes(bytes, &mut trits);
+    trits
+}
+
+/// Tries to convert arbitrary slices of bytes to trits.
+#[cfg(feature = "alloc")]
+pub fn try_from_bytes(bytes: &[u8]) -> Result<Vec<Trit>, ConversionError> {
+    check_len_multiple_of(bytes.len(), 2)?;
+    check_bytes(bytes)?;
 
-    for i in 0..(trits.len() / 9) {
-        let pos = 2 * i;
+    let mut trits = vec![Trit::Zero; bytes.len() / 2 * 9];
+    write_from_bytes(bytes, &mut trits);
+    Ok(trits)
+}
+
+/// Converts arbitrary slices of bytes to trits, writing them into `trits`.
+///
+/// Returns the number of trits written.
+pub fn from_bytes_into(bytes: &[u8], trits: &mut [Trit]) -> Result<usize, ConversionError> {
+    check_len_multiple_of(bytes.len(), 2)?;
+    check_bytes(bytes)?;
 
-        let b0 = bytes[pos] as usize;
-        let b1 = bytes[pos + 1] as usize;
+    let len = bytes.len() / 2 * 9;
+    check_buf_len(trits.len(), len)?;
+
+    write_from_bytes(bytes, &mut trits[..len]);
+    Ok(len)
+}
+
+/// Converts arbitrary slices of bytes to a `heapless::Vec` of trits.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_bytes_heapless<const N: usize>(
+    bytes: &[u8],
+) -> Result<heapless::Vec<Trit, N>, ConversionError> {
+    into_heapless(|buf| from_bytes_into(bytes, buf))
+}
+
+#[inline]
+pub(crate) fn write_from_bytes(bytes: &[u8], trits: &mut [Trit]) {
+    for i in 0..(trits.len() / 9) {
+        let b0 = bytes[2 * i] as usize;
+        let b1 = bytes[2 * i + 1] as usize;
 
         let offset = i * 9;
         trits[offset..offset + 3].copy_from_slice(&TRYTE_CODE_TO_TRITS[b0 / 8][..]);
@@ -69,106 +139,492 @@ pub fn from_bytes(bytes: &[u8]) -> Vec<Trit> {
         trits[(offset + 6)..(offset + 9)]
             .copy_from_slice(&TRYTE_CODE_TO_TRITS[b0 % 8 + 8 * (b1 % 8)]);
     }
+}
+
+/// Converts a tryte string of `N / 3` trytes to an array of trits.
+///
+/// `N` has to be a multiple of 3, which is checked at compile time.
+pub fn from_tryte_str_array<const N: usize>(tryte_str: &str) -> [Trit; N] {
+    assert_len_ratio::<N, N, 3, 3>();
+
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_tryte_str(tryte_str).unwrap();
+        check_len(tryte_str.len(), N / 3).unwrap();
+    }
 
+    let mut trits = [Trit::Zero; N];
+    write_from_tryte_str(tryte_str, &mut trits);
     trits
 }
 
+/// Tries to convert a tryte string of `N / 3` trytes to an array of trits.
+///
+/// `N` has to be a multiple of 3, which is checked at compile time.
+pub fn try_from_tryte_str_array<const N: usize>(
+    tryte_str: &str,
+) -> Result<[Trit; N], ConversionError> {
+    assert_len_ratio::<N, N, 3, 3>();
+
+    check_tryte_str(tryte_str)?;
+    check_len(tryte_str.len(), N / 3)?;
+
+    let mut trits = [Trit::Zero; N];
+    write_from_tryte_str(tryte_str, &mut trits);
+    Ok(trits)
+}
+
 macro_rules! from_tryte_str_conv {
-    ($func_name:ident, $length:expr) => {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
         /// Converts fixed-length slices of tryte strings to trits.
         pub fn $func_name(tryte_str: &str) -> [Trit; $length] {
-            #[cfg(not(feature = "no_checks"))]
-            {
-                assert!(crate::util::is_tryte_str(tryte_str));
-                assert_eq!($length, tryte_str.len() * 3);
-            }
-
-            let trytes = tryte_str.as_bytes();
-            let mut trits = [0i8; $length];
-
-            trytes.iter().enumerate().for_each(|(i, c)| {
-                trits[(i * 3)..(i * 3) + 3]
-                    .copy_from_slice(&TRYTE_CODE_TO_TRITS[ASCII_CODE_TO_TRYTE_CODE[c]][..]);
-            });
+            from_tryte_str_array(tryte_str)
+        }
 
-            trits
+        /// Tries to convert fixed-length slices of tryte strings to trits.
+        pub fn $try_func_name(tryte_str: &str) -> Result<[Trit; $length], ConversionError> {
+            try_from_tryte_str_array(tryte_str)
         }
     };
 }
 
-from_tryte_str_conv!(from_tryte_str_all, TRANSACTION_SIZE_TRITS);
-from_tryte_str_conv!(from_tryte_str_sig, SIG_MSG_FRG_SIZE_TRITS);
-from_tryte_str_conv!(from_tryte_str_81, 243);
-from_tryte_str_conv!(from_tryte_str_27, 81);
-from_tryte_str_conv!(from_tryte_str_9, 27);
+from_tryte_str_conv!(from_tryte_str_all, try_from_tryte_str_all, TRANSACTION_SIZE_TRITS);
+from_tryte_str_conv!(from_tryte_str_sig, try_from_tryte_str_sig, SIG_MSG_FRG_SIZE_TRITS);
+from_tryte_str_conv!(from_tryte_str_81, try_from_tryte_str_81, 243);
+from_tryte_str_conv!(from_tryte_str_27, try_from_tryte_str_27, 81);
+from_tryte_str_conv!(from_tryte_str_9, try_from_tryte_str_9, 27);
+
+/// Converts a tryte string of `N / 3` trytes to an array of trits in a `const` context.
+///
+/// Fails to compile if the string is not made of `N / 3` trytes. Used by the `trits!` macro.
+pub const fn from_tryte_str_const<const N: usize>(tryte_str: &str) -> [Trit; N] {
+    let bytes = tryte_str.as_bytes();
+    assert!(bytes.len() * 3 == N, "tryte string has the wrong length");
+
+    let mut trits = [Trit::Zero; N];
+    let mut i = 0;
+    while i < bytes.len() {
+        let triplet = match Tryte::from_byte(bytes[i]) {
+            Some(tryte) => from_tryte(tryte),
+            None => panic!("invalid tryte in tryte string"),
+        };
+        trits[i * 3] = triplet[0];
+        trits[i * 3 + 1] = triplet[1];
+        trits[i * 3 + 2] = triplet[2];
+        i += 1;
+    }
+    trits
+}
 
 /// Converts arbitrary slices of tryte strings to trits.
+#[cfg(feature = "alloc")]
 pub fn from_tryte_str(tryte_str: &str) -> Vec<Trit> {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert!(crate::util::is_tryte_str(tryte_str));
+        check_tryte_str(tryte_str).unwrap();
     }
-    let bytes = tryte_str.as_bytes();
 
-    let mut trits = vec![0_i8; tryte_str.len() * 3];
+    let mut trits = vec![Trit::Zero; tryte_str.len() * 3];
+    write_from_tryte_str(tryte_str, &mut trits);
+    trits
+}
+
+/// Tries to convert arbitrary slices of tryte strings to trits.
+#[cfg(feature = "alloc")]
+pub fn try_from_tryte_str(tryte_str: &str) -> Result<Vec<Trit>, ConversionError> {
+    check_tryte_str(tryte_str)?;
+
+    let mut trits = vec![Trit::Zero; tryte_str.len() * 3];
+    write_from_tryte_str(tryte_str, &mut trits);
+    Ok(trits)
+}
+
+/// Converts arbitrary slices of tryte strings to trits, writing them into `trits`.
+///
+/// Returns the number of trits written.
+pub fn from_tryte_str_into(tryte_str: &str, trits: &mut [Trit]) -> Result<usize, ConversionError> {
+    check_tryte_str(tryte_str)?;
 
-    bytes.iter().enumerate().for_each(|(i, c)| {
+    let len = tryte_str.len() * 3;
+    check_buf_len(trits.len(), len)?;
+
+    write_from_tryte_str(tryte_str, &mut trits[..len]);
+    Ok(len)
+}
+
+/// Converts arbitrary slices of tryte strings to a `heapless::Vec` of trits.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_tryte_str_heapless<const N: usize>(
+    tryte_str: &str,
+) -> Result<heapless::Vec<Trit, N>, ConversionError> {
+    into_heapless(|buf| from_tryte_str_into(tryte_str, buf))
+}
+
+#[inline]
+fn write_from_tryte_str(tryte_str: &str, trits: &mut [Trit]) {
+    tryte_str.as_bytes().iter().enumerate().for_each(|(i, &c)| {
         trits[(i * 3)..(i * 3) + 3]
-            .copy_from_slice(&TRYTE_CODE_TO_TRITS[ASCII_CODE_TO_TRYTE_CODE[c]][..]);
+            .copy_from_slice(&ASCII_CODE_TO_TRITS[(c - ASCII_CODE_OFFSET) as usize][..]);
     });
+}
+
+/// Converts an array of trytes to an array of trits.
+///
+/// `M` has to be `N * 3`, which is checked at compile time.
+pub fn from_trytes_array<const N: usize, const M: usize>(trytes: &[Tryte; N]) -> [Trit; M] {
+    assert_len_ratio::<N, M, 3, 1>();
 
+    let mut trits = [Trit::Zero; M];
+    simd::trytes_to_trits(trytes, &mut trits);
     trits
 }
 
 macro_rules! from_trytes_conv {
-    ($func_name:ident, $length:expr) => {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
         /// Converts fixed-length slices of trytes to trits.
         pub fn $func_name(trytes: &[Tryte]) -> [Trit; $length] {
-            #[cfg(not(feature = "no_checks"))]
-            {
-                assert!(crate::util::is_trytes(trytes));
-                assert_eq!($length, trytes.len() * 3);
-            }
-
-            let mut trits = [0_i8; $length];
-            trytes.iter().enumerate().for_each(|(i, t)| {
-                trits[(i * 3)..(i * 3 + 3)].copy_from_slice(&ASCII_CODE_TO_TRITS[t][..]);
-            });
-
-            trits
+            from_trytes_array::<{ $length / 3 }, $length>(as_array(trytes).unwrap())
+        }
+
+        /// Tries to convert fixed-length slices of trytes to trits.
+        pub fn $try_func_name(trytes: &[Tryte]) -> Result<[Trit; $length], ConversionError> {
+            Ok(from_trytes_array::<{ $length / 3 }, $length>(as_array(trytes)?))
         }
     };
 }
 
-from_trytes_conv!(from_trytes_all, TRANSACTION_SIZE_TRITS);
-from_trytes_conv!(from_trytes_sig, SIG_MSG_FRG_SIZE_TRITS);
-from_trytes_conv!(from_trytes_81, 243);
-from_trytes_conv!(from_trytes_27, 81);
-from_trytes_conv!(from_trytes_9, 27);
+from_trytes_conv!(from_trytes_all, try_from_trytes_all, TRANSACTION_SIZE_TRITS);
+from_trytes_conv!(from_trytes_sig, try_from_trytes_sig, SIG_MSG_FRG_SIZE_TRITS);
+from_trytes_conv!(from_trytes_81, try_from_trytes_81, 243);
+from_trytes_conv!(from_trytes_27, try_from_trytes_27, 81);
+from_trytes_conv!(from_trytes_9, try_from_trytes_9, 27);
 
 /// Converts arbitrary slices of trytes to trits.
+#[cfg(feature = "alloc")]
 pub fn from_trytes(trytes: &[Tryte]) -> Vec<Trit> {
-    let mut trits = vec![0_i8; trytes.len() * 3];
+    let mut trits = vec![Trit::Zero; trytes.len() * 3];
+    write_from_trytes(trytes, &mut trits);
+    trits
+}
+
+/// Converts arbitrary slices of trytes to trits, writing them into `trits`.
+///
+/// Returns the number of trits written.
+pub fn from_trytes_into(trytes: &[Tryte], trits: &mut [Trit]) -> Result<usize, ConversionError> {
+    let len = trytes.len() * 3;
+    check_buf_len(trits.len(), len)?;
+
+    write_from_trytes(trytes, &mut trits[..len]);
+    Ok(len)
+}
+
+/// Converts arbitrary slices of trytes to a `heapless::Vec` of trits.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_trytes_heapless<const N: usize>(
+    trytes: &[Tryte],
+) -> Result<heapless::Vec<Trit, N>, ConversionError> {
+    into_heapless(|buf| from_trytes_into(trytes, buf))
+}
 
+#[inline]
+pub(crate) fn write_from_trytes(trytes: &[Tryte], trits: &mut [Trit]) {
     trytes.iter().enumerate().for_each(|(i, t)| {
-        trits[(i * 3)..(i * 3 + 3)].copy_from_slice(&ASCII_CODE_TO_TRITS[t][..]);
+        trits[(i * 3)..(i * 3 + 3)].copy_from_slice(&from_tryte(*t));
     });
+}
+
+/// Converts a single tryte to its three trits.
+pub const fn from_tryte(tryte: Tryte) -> [Trit; 3] {
+    ASCII_CODE_TO_TRITS[(tryte.0 - ASCII_CODE_OFFSET) as usize]
+}
+
+/// Converts a slice of `i8`s holding `-1`, `0` or `1` to trits.
+#[cfg(feature = "alloc")]
+pub fn from_raw(raw: &[i8]) -> Vec<Trit> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_trits(raw).unwrap();
+    }
+
+    raw.iter().map(|&t| raw_to_trit(t)).collect()
+}
 
+/// Tries to convert a slice of `i8`s holding `-1`, `0` or `1` to trits.
+#[cfg(feature = "alloc")]
+pub fn try_from_raw(raw: &[i8]) -> Result<Vec<Trit>, ConversionError> {
+    check_trits(raw)?;
+
+    Ok(raw.iter().map(|&t| raw_to_trit(t)).collect())
+}
+
+/// Converts a slice of `i8`s holding `-1`, `0` or `1` to trits, writing them into `trits`.
+///
+/// Returns the number of trits written.
+pub fn from_raw_into(raw: &[i8], trits: &mut [Trit]) -> Result<usize, ConversionError> {
+    check_trits(raw)?;
+    check_buf_len(trits.len(), raw.len())?;
+
+    trits.iter_mut().zip(raw).for_each(|(t, &r)| *t = raw_to_trit(r));
+    Ok(raw.len())
+}
+
+/// Converts a slice of `i8`s holding `-1`, `0` or `1` to a `heapless::Vec` of trits.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_raw_heapless<const N: usize>(
+    raw: &[i8],
+) -> Result<heapless::Vec<Trit, N>, ConversionError> {
+    into_heapless(|buf| from_raw_into(raw, buf))
+}
+
+#[inline]
+fn raw_to_trit(raw: i8) -> Trit {
+    match raw {
+        -1 => Trit::Neg,
+        1 => Trit::Pos,
+        _ => Trit::Zero,
+    }
+}
+
+/// Converts an `S129` number to 81 trits.
+///
+/// This function will panic if the number is out of the range of 81 trits, `±(3^81-1)/2`.
+pub fn from_s129_to_81(number: S129) -> [Trit; TRIT_LENGTH_FOR_MAX_S129] {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_s129_range(number).unwrap();
+    }
+
+    let mut trits = [Trit::Zero; TRIT_LENGTH_FOR_MAX_S129];
+    write_from_s129(number, &mut trits);
     trits
 }
 
+/// Tries to convert an `S129` number to 81 trits.
+///
+/// Fails if the number is out of the range of 81 trits, `±(3^81-1)/2`.
+pub fn try_from_s129_to_81(
+    number: S129,
+) -> Result<[Trit; TRIT_LENGTH_FOR_MAX_S129], ConversionError> {
+    check_s129_range(number)?;
+
+    let mut trits = [Trit::Zero; TRIT_LENGTH_FOR_MAX_S129];
+    write_from_s129(number, &mut trits);
+    Ok(trits)
+}
+
+/// Converts an `i128` number to 81 trits.
+///
+/// Every `i128` is in the range of 81 trits.
+pub fn from_i128_to_81(number: i128) -> [Trit; TRIT_LENGTH_FOR_MAX_S129] {
+    let mut trits = [Trit::Zero; TRIT_LENGTH_FOR_MAX_S129];
+    write_from_s129(S129::from(number), &mut trits);
+    trits
+}
+
+/// Converts an `S129` number to the smallest number of trits that can represent it.
+///
+/// This function will panic if the number is out of the range of 81 trits, `±(3^81-1)/2`.
+#[cfg(feature = "alloc")]
+pub fn from_s129(number: S129) -> Vec<Trit> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_s129_range(number).unwrap();
+    }
+
+    let mut trits = vec![Trit::Zero; s129_len(number)];
+    write_from_s129(number, &mut trits);
+    trits
+}
+
+/// Tries to convert an `S129` number to the smallest number of trits that can represent it.
+///
+/// Fails if the number is out of the range of 81 trits, `±(3^81-1)/2`.
+#[cfg(feature = "alloc")]
+pub fn try_from_s129(number: S129) -> Result<Vec<Trit>, ConversionError> {
+    check_s129_range(number)?;
+
+    let mut trits = vec![Trit::Zero; s129_len(number)];
+    write_from_s129(number, &mut trits);
+    Ok(trits)
+}
+
+/// Converts an `i128` number to the smallest number of trits that can represent it.
+#[cfg(feature = "alloc")]
+pub fn from_i128(number: i128) -> Vec<Trit> {
+    from_s129(S129::from(number))
+}
+
+/// Converts an `S129` number to the smallest number of trits that can represent it, writing them
+/// into `trits`.
+///
+/// Returns the number of trits written.
+pub fn from_s129_into(number: S129, trits: &mut [Trit]) -> Result<usize, ConversionError> {
+    check_s129_range(number)?;
+
+    let len = s129_len(number);
+    check_buf_len(trits.len(), len)?;
+
+    write_from_s129(number, &mut trits[..len]);
+    Ok(len)
+}
+
+/// Makes sure the number can be represented by 81 trits.
+pub(crate) fn check_s129_range(number: S129) -> Result<(), ConversionError> {
+    if number.1 > MAX_S129_TRITS_ABS {
+        return Err(ConversionError::new(ErrorKind::OutOfRange, 0));
+    }
+    Ok(())
+}
+
+/// Returns the smallest number of trits that can represent the number.
+pub(crate) fn s129_len(number: S129) -> usize {
+    let mut magnitude = number.1;
+    let mut len = 0;
+
+    while magnitude != 0 {
+        magnitude = magnitude / 3 + u128::from(magnitude % 3 == 2);
+        len += 1;
+    }
+
+    len
+}
+
+/// Writes the balanced ternary representation of the number, least significant trit first,
+/// filling up the remaining trits with zeros.
+#[inline]
+pub(crate) fn write_from_s129(number: S129, trits: &mut [Trit]) {
+    let (pos, neg) =
+        if number.is_negative() { (Trit::Neg, Trit::Pos) } else { (Trit::Pos, Trit::Neg) };
+    let mut magnitude = number.1;
+
+    for trit in trits.iter_mut() {
+        *trit = match magnitude % 3 {
+            1 => pos,
+            2 => neg,
+            _ => Trit::Zero,
+        };
+        magnitude = magnitude / 3 + u128::from(magnitude % 3 == 2);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ErrorKind;
 
     #[test]
     fn from_tryte_string_test() {
         //
         //println!("{:?}", from_tryte_str("AAA"));
-        assert_eq!(&[1, 0, 0, 1, 0, 0, 1, 0, 0], &from_tryte_str("AAA")[..]);
+        assert_eq!(from_raw(&[1, 0, 0, 1, 0, 0, 1, 0, 0]), from_tryte_str("AAA"));
 
         //println!("{:?}", from_tryte_str("SEG"));
-        assert_eq!(&[1, 0, -1, -1, -1, 1, 1, -1, 1], &from_tryte_str("SEG")[..]);
+        assert_eq!(from_raw(&[1, 0, -1, -1, -1, 1, 1, -1, 1]), from_tryte_str("SEG"));
+    }
+
+    #[test]
+    fn try_from_tryte_str_test() {
+        assert_eq!(from_raw(&[1, 0, 0, 0, 0, 0]), try_from_tryte_str("A9").unwrap());
+
+        let err = try_from_tryte_str("AB8D").unwrap_err();
+        assert_eq!(ErrorKind::InvalidTryte, err.kind());
+        assert_eq!(2, err.index());
+    }
+
+    #[test]
+    fn try_from_tryte_str_9_test() {
+        assert!(try_from_tryte_str_9("SEGSEGSEG").is_ok());
+
+        let err = try_from_tryte_str_9("SEG").unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(3, err.index());
+    }
+
+    #[test]
+    fn try_from_bytes_test() {
+        assert_eq!(from_bytes(&[12, 34]), try_from_bytes(&[12, 34]).unwrap());
+
+        let err = try_from_bytes(&[0, 0, 0]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+
+        let err = try_from_bytes(&[0, 0, 255, 0]).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(2, err.index());
+    }
+
+    #[test]
+    fn from_array_test() {
+        let trits: [Trit; 9] = from_trytes_array(&[Tryte(b'S'), Tryte(b'E'), Tryte(b'G')]);
+        assert_eq!(from_tryte_str("SEG"), trits);
+
+        let trits: [Trit; 9] = from_tryte_str_array("SEG");
+        assert_eq!(from_tryte_str("SEG"), trits);
+
+        let trits: [Trit; 9] = from_bytes_array(&[12, 34]);
+        assert_eq!(from_bytes(&[12, 34]), trits);
+
+        let err = try_from_bytes_array::<2, 9>(&[255, 0]).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert_eq!(0, err.index());
     }
 
+    #[test]
+    fn from_tryte_test() {
+        const SEG: [[Trit; 3]; 3] =
+            [from_tryte(Tryte(b'S')), from_tryte(Tryte(b'E')), from_tryte(Tryte(b'G'))];
+        assert_eq!(from_tryte_str("SEG"), SEG.concat());
+
+        for (i, &tryte) in crate::luts::TRYTE_CODE_TO_ASCII_CODE.iter().enumerate() {
+            assert_eq!(TRYTE_CODE_TO_TRITS[i], from_tryte(tryte));
+            assert_eq!(tryte, crate::trytes::from_trit_triplet(from_tryte(tryte)));
+            assert_eq!(Some(tryte), Tryte::from_byte(tryte.as_byte()));
+        }
+        assert_eq!(None, Tryte::from_byte(b'a'));
+    }
+
+    #[test]
+    fn try_from_raw_test() {
+        assert_eq!(Ok(vec![Trit::Neg, Trit::Zero, Trit::Pos]), try_from_raw(&[-1, 0, 1]));
+
+        let err = try_from_raw(&[-1, 0, 1, 2]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidTrit, err.kind());
+        assert_eq!(3, err.index());
+    }
+
+    #[test]
+    fn into_test() {
+        let mut trits = [Trit::Zero; 12];
+
+        assert_eq!(Ok(9), from_bytes_into(&[12, 34], &mut trits));
+        assert_eq!(from_bytes(&[12, 34]), trits[..9]);
+
+        assert_eq!(Ok(9), from_tryte_str_into("SEG", &mut trits));
+        assert_eq!(from_tryte_str("SEG"), trits[..9]);
+
+        assert_eq!(Ok(6), from_trytes_into(&[Tryte(b'A'), Tryte(b'Z')], &mut trits));
+        assert_eq!(from_tryte_str("AZ"), trits[..6]);
+
+        assert_eq!(Ok(3), from_raw_into(&[-1, 0, 1], &mut trits));
+        assert_eq!(from_raw(&[-1, 0, 1]), trits[..3]);
+
+        let err = from_tryte_str_into("SEGSEG", &mut trits).unwrap_err();
+        assert_eq!(ErrorKind::BufferTooSmall, err.kind());
+        assert_eq!(18, err.index());
+    }
+
+    #[test]
+    fn heapless_test() {
+        let trits = from_tryte_str_heapless::<9>("SEG").unwrap();
+        assert_eq!(from_tryte_str("SEG"), &trits[..]);
+
+        let trits = from_bytes_heapless::<9>(&[12, 34]).unwrap();
+        assert_eq!(from_bytes(&[12, 34]), &trits[..]);
+
+        let err = from_tryte_str_heapless::<9>("SEGS").unwrap_err();
+        assert_eq!(ErrorKind::BufferTooSmall, err.kind());
+        assert_eq!(12, err.index());
+    }
 }
@@ -1,39 +1,128 @@
 //! Converter functions that convert trits/trytes and other datatypes to tryte strings.
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::string::String;
 
-use crate::{trytes, types::Trit, types::Tryte};
+use crate::{
+    error::ConversionError,
+    trytes,
+    types::Trit,
+    types::Tryte,
+    util::check_ascii,
+    util::check_buf_len,
+    util::check_len_multiple_of,
+    util::into_heapless_string,
+};
 
 /// Converts a slice of trits to a tryte string.
+#[cfg(feature = "alloc")]
 pub fn from_trits(trits: &[Trit]) -> String {
-    String::from_utf8(trytes::from_trits(&trits)).unwrap()
+    from_trytes(&trytes::from_trits(trits))
 }
 
 #[cfg(test)]
 #[test]
 fn test_from_trits() {
-    assert_eq!("A", from_trits(&[1, 0, 0]));
-    assert_eq!("M", from_trits(&[1, 1, 1]));
-    assert_eq!("9A", from_trits(&[0, 0, 0, 1, 0, 0]));
+    assert_eq!("A", from_trits(&crate::trits::from_raw(&[1, 0, 0])));
+    assert_eq!("M", from_trits(&crate::trits::from_raw(&[1, 1, 1])));
+    assert_eq!("9A", from_trits(&crate::trits::from_raw(&[0, 0, 0, 1, 0, 0])));
+}
+
+/// Tries to convert a slice of trits to a tryte string.
+#[cfg(feature = "alloc")]
+pub fn try_from_trits(trits: &[Trit]) -> Result<String, ConversionError> {
+    Ok(from_trytes(&trytes::try_from_trits(trits)?))
+}
+
+#[cfg(test)]
+#[test]
+fn test_try_from_trits() {
+    let trits = crate::trits::from_raw(&[0, 0, 0, 1, 0, 0]);
+    assert_eq!(Ok(String::from("9A")), try_from_trits(&trits));
+    assert!(try_from_trits(&trits[..5]).is_err());
+}
+
+/// Converts a slice of trits to a tryte string, writing its characters into `tryte_str`.
+///
+/// Returns the number of characters written.
+pub fn from_trits_into(trits: &[Trit], tryte_str: &mut [u8]) -> Result<usize, ConversionError> {
+    check_len_multiple_of(trits.len(), 3)?;
+
+    let len = trits.len() / 3;
+    check_buf_len(tryte_str.len(), len)?;
+
+    for (c, triplet) in tryte_str.iter_mut().zip(trits.chunks_exact(3)) {
+        *c = trytes::from_trit_triplet([triplet[0], triplet[1], triplet[2]]).as_byte();
+    }
+    Ok(len)
+}
+
+/// Converts a slice of trits to a tryte string held by a `heapless::String`.
+///
+/// Fails if the string doesn't fit into its capacity `N`.
+pub fn from_trits_heapless<const N: usize>(
+    trits: &[Trit],
+) -> Result<heapless::String<N>, ConversionError> {
+    into_heapless_string(|buf| from_trits_into(trits, buf))
+}
+
+#[cfg(test)]
+#[test]
+fn test_from_trits_into() {
+    let mut tryte_str = [0_u8; 2];
+    assert_eq!(
+        Ok(2),
+        from_trits_into(&crate::trits::from_raw(&[0, 0, 0, 1, 0, 0]), &mut tryte_str)
+    );
+    assert_eq!(b"9A", &tryte_str);
+    assert!(from_trits_into(&[Trit::Zero; 9], &mut tryte_str).is_err());
 }
 
 /// Converts a slice of trytes to a tryte string.
+#[cfg(feature = "alloc")]
 pub fn from_trytes(trytes: &[Tryte]) -> String {
-    String::from_utf8(trytes.to_vec()).unwrap()
+    trytes.iter().map(|t| t.as_char()).collect()
 }
 
 #[cfg(test)]
 #[test]
 fn test_from_trytes() {
-    assert_eq!("A", from_trytes(&['A' as u8]));
-    assert_eq!("M", from_trytes(&['M' as u8]));
-    assert_eq!("9A", from_trytes(&['9' as u8, 'A' as u8]));
+    assert_eq!("A", from_trytes(&[Tryte(b'A')]));
+    assert_eq!("M", from_trytes(&[Tryte(b'M')]));
+    assert_eq!("9A", from_trytes(&[Tryte(b'9'), Tryte(b'A')]));
+}
+
+/// Converts a slice of trytes to a tryte string, writing its characters into `tryte_str`.
+///
+/// Returns the number of characters written.
+pub fn from_trytes_into(trytes: &[Tryte], tryte_str: &mut [u8]) -> Result<usize, ConversionError> {
+    check_buf_len(tryte_str.len(), trytes.len())?;
+
+    tryte_str.iter_mut().zip(trytes).for_each(|(c, t)| *c = t.as_byte());
+    Ok(trytes.len())
+}
+
+/// Converts a slice of trytes to a tryte string held by a `heapless::String`.
+///
+/// Fails if the string doesn't fit into its capacity `N`.
+pub fn from_trytes_heapless<const N: usize>(
+    trytes: &[Tryte],
+) -> Result<heapless::String<N>, ConversionError> {
+    into_heapless_string(|buf| from_trytes_into(trytes, buf))
+}
+
+#[cfg(test)]
+#[test]
+fn test_from_trytes_into() {
+    let mut tryte_str = [0_u8; 3];
+    assert_eq!(Ok(2), from_trytes_into(&[Tryte(b'9'), Tryte(b'A')], &mut tryte_str));
+    assert_eq!(b"9A", &tryte_str[..2]);
 }
 
 /// Converts an ASCII string to a tryte string.
+#[cfg(feature = "alloc")]
 pub fn from_ascii(ascii_str: &str) -> String {
-    String::from_utf8(trytes::from_ascii(ascii_str)).unwrap()
+    from_trytes(&trytes::from_ascii(ascii_str))
 }
 
 #[cfg(test)]
@@ -41,3 +130,51 @@ pub fn from_ascii(ascii_str: &str) -> String {
 fn test_from_ascii() {
     assert_eq!("YEZNMEQWF", from_ascii("Hello"));
 }
+
+/// Tries to convert an ASCII string to a tryte string.
+#[cfg(feature = "alloc")]
+pub fn try_from_ascii(ascii_str: &str) -> Result<String, ConversionError> {
+    Ok(from_trytes(&trytes::try_from_ascii(ascii_str)?))
+}
+
+#[cfg(test)]
+#[test]
+fn test_try_from_ascii() {
+    assert_eq!(Ok(String::from("YEZNMEQWF")), try_from_ascii("Hello"));
+    assert!(try_from_ascii("Hallö").is_err());
+}
+
+/// Converts an ASCII string to a tryte string, writing its characters into `tryte_str`.
+///
+/// Returns the number of characters written.
+pub fn from_ascii_into(ascii_str: &str, tryte_str: &mut [u8]) -> Result<usize, ConversionError> {
+    check_ascii(ascii_str)?;
+
+    let len = ascii_str.len().div_ceil(2) * 3;
+    check_buf_len(tryte_str.len(), len)?;
+
+    for (chars, pair) in tryte_str.chunks_exact_mut(3).zip(ascii_str.as_bytes().chunks(2)) {
+        for (c, t) in chars.iter_mut().zip(&trytes::from_ascii_pair(pair)) {
+            *c = t.as_byte();
+        }
+    }
+    Ok(len)
+}
+
+/// Converts an ASCII string to a tryte string held by a `heapless::String`.
+///
+/// Fails if the string doesn't fit into its capacity `N`.
+pub fn from_ascii_heapless<const N: usize>(
+    ascii_str: &str,
+) -> Result<heapless::String<N>, ConversionError> {
+    into_heapless_string(|buf| from_ascii_into(ascii_str, buf))
+}
+
+#[cfg(test)]
+#[test]
+fn test_from_ascii_into() {
+    let mut tryte_str = [0_u8; 9];
+    assert_eq!(Ok(9), from_ascii_into("Hello", &mut tryte_str));
+    assert_eq!(b"YEZNMEQWF", &tryte_str);
+    assert!(from_ascii_into("Hello!!", &mut tryte_str).is_err());
+}
@@ -1,59 +1,137 @@
 //! Converter functions that convert various datatypes to Trytes.
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::vec::Vec;
 
 use crate::{
     constants::MAX_TRYTE_TRIPLET_ABS,
     constants::SIG_MSG_FRG_SIZE_TRYTES,
     constants::TRANSACTION_SIZE_TRYTES,
-    constants::TRYTE_LENGTH_FOR_MAX_I64,
+    constants::TRIT_LENGTH_FOR_MAX_S129,
+    constants::TRYTE_LENGTH_FOR_MAX_S129,
+    error::ConversionError,
+    error::ErrorKind,
     luts::TRYTE_CODE_TO_ASCII_CODE,
     luts::TRYTE_CODE_TO_ASCII_CODE_NEG,
+    simd,
+    trits::check_s129_range,
+    trits::s129_len,
+    trits::write_from_s129,
     types::Trit,
     types::Tryte,
+    types::S129,
+    util::as_array,
+    util::assert_len_ratio,
+    util::check_ascii,
+    util::check_buf_len,
+    util::check_bytes,
+    util::check_len,
+    util::check_len_multiple_of,
+    util::check_tryte_str,
+    util::into_heapless,
 };
 
+/// Converts an array of bytes to an array of trytes.
+///
+/// `M` has to be `N / 2 * 3`, which is checked at compile time.
+pub fn from_bytes_array<const N: usize, const M: usize>(bytes: &[u8; N]) -> [Tryte; M] {
+    assert_len_ratio::<N, M, 3, 2>();
+
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_bytes(bytes).unwrap();
+    }
+
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; M];
+    simd::bytes_to_trytes(bytes, &mut trytes);
+    trytes
+}
+
+/// Tries to convert an array of bytes to an array of trytes.
+///
+/// `M` has to be `N / 2 * 3`, which is checked at compile time.
+pub fn try_from_bytes_array<const N: usize, const M: usize>(
+    bytes: &[u8; N],
+) -> Result<[Tryte; M], ConversionError> {
+    assert_len_ratio::<N, M, 3, 2>();
+
+    check_bytes(bytes)?;
+
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; M];
+    simd::bytes_to_trytes(bytes, &mut trytes);
+    Ok(trytes)
+}
+
 macro_rules! from_bytes_conv {
-    ($func_name:ident, $length:expr) => {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
         /// Converts a slice of bytes to `[Tryte; $length]`.
         pub fn $func_name(bytes: &[u8]) -> [Tryte; $length] {
-            #[cfg(not(feature = "no_checks"))]
-            {
-                assert_eq!(0, bytes.len() % 2);
-                assert_eq!($length, bytes.len() / 2 * 3);
-            }
-
-            let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; $length];
-
-            for i in 0..($length / 3) {
-                let b0 = bytes[2 * i] as usize;
-                let b1 = bytes[2 * i + 1] as usize;
-
-                trytes[3 * i] = TRYTE_CODE_TO_ASCII_CODE[b0 / 8];
-                trytes[3 * i + 1] = TRYTE_CODE_TO_ASCII_CODE[b1 / 8];
-                trytes[3 * i + 2] = TRYTE_CODE_TO_ASCII_CODE[b0 % 8 + 8 * (b1 % 8)];
-            }
-            trytes
+            from_bytes_array::<{ $length / 3 * 2 }, $length>(as_array(bytes).unwrap())
+        }
+
+        /// Tries to convert a slice of bytes to `[Tryte; $length]`.
+        pub fn $try_func_name(bytes: &[u8]) -> Result<[Tryte; $length], ConversionError> {
+            try_from_bytes_array::<{ $length / 3 * 2 }, $length>(as_array(bytes)?)
         }
     };
 }
 
-from_bytes_conv!(from_bytes_all, TRANSACTION_SIZE_TRYTES);
-from_bytes_conv!(from_bytes_sig, SIG_MSG_FRG_SIZE_TRYTES);
-from_bytes_conv!(from_bytes_81, 81);
-from_bytes_conv!(from_bytes_27, 27);
-from_bytes_conv!(from_bytes_9, 9);
+from_bytes_conv!(from_bytes_all, try_from_bytes_all, TRANSACTION_SIZE_TRYTES);
+from_bytes_conv!(from_bytes_sig, try_from_bytes_sig, SIG_MSG_FRG_SIZE_TRYTES);
+from_bytes_conv!(from_bytes_81, try_from_bytes_81, 81);
+from_bytes_conv!(from_bytes_27, try_from_bytes_27, 27);
+from_bytes_conv!(from_bytes_9, try_from_bytes_9, 9);
 
 /// Converts a slice of bytes to trytes.
+#[cfg(feature = "alloc")]
 pub fn from_bytes(bytes: &[u8]) -> Vec<Tryte> {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert_eq!(0, bytes.len() % 2);
+        check_len_multiple_of(bytes.len(), 2).unwrap();
+        check_bytes(bytes).unwrap();
     }
 
     let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; bytes.len() / 2 * 3];
+    write_from_bytes(bytes, &mut trytes);
+    trytes
+}
+
+/// Tries to convert a slice of bytes to trytes.
+#[cfg(feature = "alloc")]
+pub fn try_from_bytes(bytes: &[u8]) -> Result<Vec<Tryte>, ConversionError> {
+    check_len_multiple_of(bytes.len(), 2)?;
+    check_bytes(bytes)?;
+
+    let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; bytes.len() / 2 * 3];
+    write_from_bytes(bytes, &mut trytes);
+    Ok(trytes)
+}
+
+/// Converts a slice of bytes to trytes, writing them into `trytes`.
+///
+/// Returns the number of trytes written.
+pub fn from_bytes_into(bytes: &[u8], trytes: &mut [Tryte]) -> Result<usize, ConversionError> {
+    check_len_multiple_of(bytes.len(), 2)?;
+    check_bytes(bytes)?;
 
+    let len = bytes.len() / 2 * 3;
+    check_buf_len(trytes.len(), len)?;
+
+    write_from_bytes(bytes, &mut trytes[..len]);
+    Ok(len)
+}
+
+/// Converts a slice of bytes to a `heapless::Vec` of trytes.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_bytes_heapless<const N: usize>(
+    bytes: &[u8],
+) -> Result<heapless::Vec<Tryte, N>, ConversionError> {
+    into_heapless(|buf| from_bytes_into(bytes, buf))
+}
+
+#[inline]
+pub(crate) fn write_from_bytes(bytes: &[u8], trytes: &mut [Tryte]) {
     for i in 0..(trytes.len() / 3) {
         let b0 = bytes[2 * i] as usize;
         let b1 = bytes[2 * i + 1] as usize;
@@ -62,122 +140,203 @@ pub fn from_bytes(bytes: &[u8]) -> Vec<Tryte> {
         trytes[3 * i + 1] = TRYTE_CODE_TO_ASCII_CODE[b1 / 8];
         trytes[3 * i + 2] = TRYTE_CODE_TO_ASCII_CODE[b0 % 8 + 8 * (b1 % 8)];
     }
+}
+
+/// Converts an array of trits to an array of trytes.
+///
+/// `M` has to be `N / 3`, which is checked at compile time.
+pub fn from_trits_array<const N: usize, const M: usize>(trits: &[Trit; N]) -> [Tryte; M] {
+    assert_len_ratio::<N, M, 1, 3>();
+
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; M];
+    write_from_trits(trits, &mut trytes);
     trytes
 }
 
 macro_rules! from_trits_conv {
-    ($func_name:ident, $length:expr) => {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
         /// Converts fixed-sized slices of trits to trytes.
         pub fn $func_name(trits: &[Trit]) -> [Tryte; $length] {
-            #[cfg(not(feature = "no_checks"))]
-            {
-                assert_eq!(0, trits.len() % 3);
-                assert_eq!($length, trits.len() / 3);
-            }
-
-            let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; $length];
-
-            for (i, t) in trytes.iter_mut().enumerate() {
-                let mut index = trits[i * 3] + 3 * trits[i * 3 + 1] + 9 * trits[i * 3 + 2];
-                index = if index < 0 { index + 27 } else { index };
-                *t = TRYTE_CODE_TO_ASCII_CODE[index as usize];
-            }
+            from_trits_array::<{ $length * 3 }, $length>(as_array(trits).unwrap())
+        }
 
-            trytes
+        /// Tries to convert fixed-sized slices of trits to trytes.
+        pub fn $try_func_name(trits: &[Trit]) -> Result<[Tryte; $length], ConversionError> {
+            Ok(from_trits_array::<{ $length * 3 }, $length>(as_array(trits)?))
         }
     };
 }
 
-from_trits_conv!(from_trits_all, TRANSACTION_SIZE_TRYTES);
-from_trits_conv!(from_trits_sig, SIG_MSG_FRG_SIZE_TRYTES);
-from_trits_conv!(from_trits_243, 81);
-from_trits_conv!(from_trits_81, 27);
-from_trits_conv!(from_trits_27, 9);
+from_trits_conv!(from_trits_all, try_from_trits_all, TRANSACTION_SIZE_TRYTES);
+from_trits_conv!(from_trits_sig, try_from_trits_sig, SIG_MSG_FRG_SIZE_TRYTES);
+from_trits_conv!(from_trits_243, try_from_trits_243, 81);
+from_trits_conv!(from_trits_81, try_from_trits_81, 27);
+from_trits_conv!(from_trits_27, try_from_trits_27, 9);
 
 /// Converts arbitrary slices of trits to trytes.
+#[cfg(feature = "alloc")]
 pub fn from_trits(trits: &[Trit]) -> Vec<Tryte> {
     #[cfg(not(feature = "no_checks"))]
     {
-        assert_eq!(0, trits.len() % 3);
+        check_len_multiple_of(trits.len(), 3).unwrap();
     }
 
     let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; trits.len() / 3];
+    write_from_trits(trits, &mut trytes);
+    trytes
+}
+
+/// Tries to convert arbitrary slices of trits to trytes.
+#[cfg(feature = "alloc")]
+pub fn try_from_trits(trits: &[Trit]) -> Result<Vec<Tryte>, ConversionError> {
+    check_len_multiple_of(trits.len(), 3)?;
+
+    let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; trits.len() / 3];
+    write_from_trits(trits, &mut trytes);
+    Ok(trytes)
+}
+
+/// Converts arbitrary slices of trits to trytes, writing them into `trytes`.
+///
+/// Returns the number of trytes written.
+pub fn from_trits_into(trits: &[Trit], trytes: &mut [Tryte]) -> Result<usize, ConversionError> {
+    check_len_multiple_of(trits.len(), 3)?;
+
+    let len = trits.len() / 3;
+    check_buf_len(trytes.len(), len)?;
+
+    write_from_trits(trits, &mut trytes[..len]);
+    Ok(len)
+}
 
+/// Converts arbitrary slices of trits to a `heapless::Vec` of trytes.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_trits_heapless<const N: usize>(
+    trits: &[Trit],
+) -> Result<heapless::Vec<Tryte, N>, ConversionError> {
+    into_heapless(|buf| from_trits_into(trits, buf))
+}
+
+#[inline]
+fn write_from_trits(trits: &[Trit], trytes: &mut [Tryte]) {
     for (i, t) in trytes.iter_mut().enumerate() {
-        let mut index = trits[i * 3] + 3 * trits[i * 3 + 1] + 9 * trits[i * 3 + 2];
-        index = if index < 0 { index + 27 } else { index };
-        *t = TRYTE_CODE_TO_ASCII_CODE[index as usize];
+        *t = from_trit_triplet([trits[i * 3], trits[i * 3 + 1], trits[i * 3 + 2]]);
+    }
+}
+
+/// Converts three trits to a single tryte.
+pub const fn from_trit_triplet(triplet: [Trit; 3]) -> Tryte {
+    let mut index = triplet[0] as i8 + 3 * triplet[1] as i8 + 9 * triplet[2] as i8;
+    index = if index < 0 { index + 27 } else { index };
+    TRYTE_CODE_TO_ASCII_CODE[index as usize]
+}
+
+/// Converts an `i64` number to an array of `N` trytes.
+pub fn from_num_i64_array<const N: usize>(number: i64) -> [Tryte; N] {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_num_i64_range(number, N).unwrap();
     }
 
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; N];
+    write_from_num_i64(number, &mut trytes);
     trytes
 }
 
+/// Tries to convert an `i64` number to an array of `N` trytes.
+///
+/// Fails if the number can't be represented by that many trytes.
+pub fn try_from_num_i64_array<const N: usize>(number: i64) -> Result<[Tryte; N], ConversionError> {
+    check_num_i64_range(number, N)?;
+
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; N];
+    write_from_num_i64(number, &mut trytes);
+    Ok(trytes)
+}
+
 macro_rules! from_num_i64_conv {
-    ($func_name:ident, $length:expr) => {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
         /// Converts an `i64` number to a fixed number of trytes.
         pub fn $func_name(number: i64) -> [Tryte; $length] {
-            #[cfg(not(feature = "no_checks"))]
-            {
-                // make sure the number can be represented by the specified
-                // number of trytes
-                let range_abs = (3_i64.pow(TRYTE_LENGTH_FOR_MAX_I64 as u32 * 3) - 1) / 2;
-                assert!(number.abs() <= range_abs);
-            }
-
-            let lut = if number > 0 {
-                &TRYTE_CODE_TO_ASCII_CODE
-            } else {
-                &TRYTE_CODE_TO_ASCII_CODE_NEG
-            };
-
-            let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; $length];
-
-            let mut number = number.abs();
-            for tryte in trytes.iter_mut().take(TRYTE_LENGTH_FOR_MAX_I64) {
-                let remainder = number % 27;
-                number = if remainder > 13 {
-                    number / 27 + 1
-                } else {
-                    number / 27
-                };
-
-                *tryte = lut[remainder as usize];
-
-                if number == 0 {
-                    break;
-                }
-            }
-
-            trytes
+            from_num_i64_array(number)
+        }
+
+        /// Tries to convert an `i64` number to a fixed number of trytes.
+        ///
+        /// Fails if the number can't be represented by that many trytes.
+        pub fn $try_func_name(number: i64) -> Result<[Tryte; $length], ConversionError> {
+            try_from_num_i64_array(number)
         }
     };
 }
 
-from_num_i64_conv!(from_num_i64_to_13, 13);
-from_num_i64_conv!(from_num_i64_to_11, 11);
-from_num_i64_conv!(from_num_i64_to_3, 3); //TODO: don't make this public
+from_num_i64_conv!(from_num_i64_to_13, try_from_num_i64_to_13, 13);
+from_num_i64_conv!(from_num_i64_to_11, try_from_num_i64_to_11, 11);
+from_num_i64_conv!(from_num_i64_to_3, try_from_num_i64_to_3, 3); //TODO: don't make this public
 
 /// Converts a `i64` number to trytes.
+#[cfg(feature = "alloc")]
 pub fn from_num_i64(number: i64) -> Vec<Tryte> {
-    let num_trytes = ((((number.abs() as f64 * 2.0) + 1.0).log(3.0)) / 3.0).ceil() as usize;
+    let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; num_i64_len(number)];
+    write_from_num_i64(number, &mut trytes);
+    trytes
+}
 
-    let lut = if number > 0 {
-        &TRYTE_CODE_TO_ASCII_CODE
-    } else {
-        &TRYTE_CODE_TO_ASCII_CODE_NEG
-    };
+/// Converts a `i64` number to trytes, writing them into `trytes`.
+///
+/// Returns the number of trytes written.
+pub fn from_num_i64_into(number: i64, trytes: &mut [Tryte]) -> Result<usize, ConversionError> {
+    let len = num_i64_len(number);
+    check_buf_len(trytes.len(), len)?;
+
+    trytes[..len].iter_mut().for_each(|t| *t = TRYTE_CODE_TO_ASCII_CODE[0]);
+    write_from_num_i64(number, &mut trytes[..len]);
+    Ok(len)
+}
+
+/// Converts a `i64` number to a `heapless::Vec` of trytes.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_num_i64_heapless<const N: usize>(
+    number: i64,
+) -> Result<heapless::Vec<Tryte, N>, ConversionError> {
+    into_heapless(|buf| from_num_i64_into(number, buf))
+}
+
+/// Returns the smallest number of trytes that can represent the number.
+fn num_i64_len(number: i64) -> usize {
+    let number = u128::from(number.unsigned_abs());
+
+    // The largest absolute value that can be represented by `len` trytes, (27^len - 1) / 2.
+    let mut range_abs = 0_u128;
+    let mut len = 0;
+
+    while range_abs < number {
+        range_abs = range_abs * 27 + 13;
+        len += 1;
+    }
+
+    len
+}
+
+/// Makes sure the number can be represented by the specified number of trytes.
+fn check_num_i64_range(number: i64, num_trytes: usize) -> Result<(), ConversionError> {
+    if num_i64_len(number) > num_trytes {
+        return Err(ConversionError::new(ErrorKind::OutOfRange, 0));
+    }
+    Ok(())
+}
 
-    let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; num_trytes];
+#[inline]
+fn write_from_num_i64(number: i64, trytes: &mut [Tryte]) {
+    let lut = if number > 0 { &TRYTE_CODE_TO_ASCII_CODE } else { &TRYTE_CODE_TO_ASCII_CODE_NEG };
 
-    let mut number = number.abs();
+    let mut number = number.unsigned_abs();
 
     for tryte in trytes.iter_mut() {
         let remainder = number % 27;
-        number = if remainder > 13 {
-            number / 27 + 1
-        } else {
-            number / 27
-        };
+        number = if remainder > 13 { number / 27 + 1 } else { number / 27 };
 
         *tryte = lut[remainder as usize];
 
@@ -185,99 +344,288 @@ pub fn from_num_i64(number: i64) -> Vec<Tryte> {
             break;
         }
     }
+}
+
+/// Converts an `S129` number to 27 trytes.
+///
+/// This function will panic if the number is out of the range of 27 trytes, `±(3^81-1)/2`.
+pub fn from_s129_to_27(number: S129) -> [Tryte; TRYTE_LENGTH_FOR_MAX_S129] {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_s129_range(number).unwrap();
+    }
 
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; TRYTE_LENGTH_FOR_MAX_S129];
+    write_from_s129_trytes(number, &mut trytes);
     trytes
 }
 
-macro_rules! from_ascii_conv {
-    ($func_name:ident, $length:expr) => {
-        /// Converts an ASCII string to trytes.
-        pub fn $func_name(ascii_str: &str) -> [Tryte; $length] {
-            let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; $length];
+/// Tries to convert an `S129` number to 27 trytes.
+///
+/// Fails if the number is out of the range of 27 trytes, `±(3^81-1)/2`.
+pub fn try_from_s129_to_27(
+    number: S129,
+) -> Result<[Tryte; TRYTE_LENGTH_FOR_MAX_S129], ConversionError> {
+    check_s129_range(number)?;
+
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; TRYTE_LENGTH_FOR_MAX_S129];
+    write_from_s129_trytes(number, &mut trytes);
+    Ok(trytes)
+}
 
-            if ascii_str.is_empty() {
-                return trytes;
-            }
+/// Converts an `i128` number to 27 trytes.
+///
+/// Every `i128` is in the range of 27 trytes.
+pub fn from_i128_to_27(number: i128) -> [Tryte; TRYTE_LENGTH_FOR_MAX_S129] {
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; TRYTE_LENGTH_FOR_MAX_S129];
+    write_from_s129_trytes(S129::from(number), &mut trytes);
+    trytes
+}
 
-            #[cfg(not(feature = "no_checks"))]
-            {
-                assert!(ascii_str.is_ascii());
-                assert!(ascii_str.len() == $length / 3 * 2);
-            }
+/// Converts an `S129` number to the smallest number of trytes that can represent it.
+///
+/// This function will panic if the number is out of the range of 27 trytes, `±(3^81-1)/2`.
+#[cfg(feature = "alloc")]
+pub fn from_s129(number: S129) -> Vec<Tryte> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_s129_range(number).unwrap();
+    }
 
-            let mut ascii: Vec<_> = ascii_str.chars().map(|c| c as i64).collect();
+    let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; s129_len(number).div_ceil(3)];
+    write_from_s129_trytes(number, &mut trytes);
+    trytes
+}
+
+/// Tries to convert an `S129` number to the smallest number of trytes that can represent it.
+///
+/// Fails if the number is out of the range of 27 trytes, `±(3^81-1)/2`.
+#[cfg(feature = "alloc")]
+pub fn try_from_s129(number: S129) -> Result<Vec<Tryte>, ConversionError> {
+    check_s129_range(number)?;
 
-            if ascii.len() % 2 != 0 {
-                ascii.push(0);
-            }
+    let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; s129_len(number).div_ceil(3)];
+    write_from_s129_trytes(number, &mut trytes);
+    Ok(trytes)
+}
 
-            let mut tryte_index = 0;
+/// Converts an `i128` number to the smallest number of trytes that can represent it.
+#[cfg(feature = "alloc")]
+pub fn from_i128(number: i128) -> Vec<Tryte> {
+    from_s129(S129::from(number))
+}
 
-            for i in (0..(ascii.len() - 1)).step_by(2) {
-                let index = ascii[i] * 127 + ascii[i + 1] - MAX_TRYTE_TRIPLET_ABS;
+/// Converts an `S129` number to the smallest number of trytes that can represent it, writing
+/// them into `trytes`.
+///
+/// Returns the number of trytes written.
+pub fn from_s129_into(number: S129, trytes: &mut [Tryte]) -> Result<usize, ConversionError> {
+    check_s129_range(number)?;
 
-                let tryte_triplet = from_num_i64_to_3(index);
+    let len = s129_len(number).div_ceil(3);
+    check_buf_len(trytes.len(), len)?;
 
-                trytes[tryte_index] = tryte_triplet[0];
-                trytes[tryte_index + 1] = tryte_triplet[1];
-                trytes[tryte_index + 2] = tryte_triplet[2];
+    write_from_s129_trytes(number, &mut trytes[..len]);
+    Ok(len)
+}
 
-                tryte_index += 3;
-            }
+#[inline]
+fn write_from_s129_trytes(number: S129, trytes: &mut [Tryte]) {
+    let mut trits = [Trit::Zero; TRIT_LENGTH_FOR_MAX_S129];
+    write_from_s129(number, &mut trits);
 
-            trytes
-        }
-    };
+    for (tryte, triplet) in trytes.iter_mut().zip(trits.chunks_exact(3)) {
+        *tryte = from_trit_triplet([triplet[0], triplet[1], triplet[2]]);
+    }
 }
 
-from_ascii_conv!(from_ascii_18, 27);
-from_ascii_conv!(from_ascii_6, 9);
+/// Converts an ASCII string of `N / 3 * 2` characters to an array of trytes.
+///
+/// `N` has to be a multiple of 3, which is checked at compile time.
+pub fn from_ascii_array<const N: usize>(ascii_str: &str) -> [Tryte; N] {
+    assert_len_ratio::<N, N, 3, 3>();
+
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; N];
 
-/// Converts an ASCII string to trytes.
-pub fn from_ascii(ascii_str: &str) -> Vec<Tryte> {
     if ascii_str.is_empty() {
-        return vec![];
+        return trytes;
     }
 
     #[cfg(not(feature = "no_checks"))]
     {
-        assert!(ascii_str.is_ascii());
+        check_ascii(ascii_str).unwrap();
+        check_len(ascii_str.len(), N / 3 * 2).unwrap();
     }
 
-    let mut ascii: Vec<_> = ascii_str.chars().map(|c| c as i64).collect();
+    write_from_ascii(ascii_str, &mut trytes);
+    trytes
+}
+
+/// Tries to convert an ASCII string of `N / 3 * 2` characters to an array of trytes.
+///
+/// `N` has to be a multiple of 3, which is checked at compile time.
+pub fn try_from_ascii_array<const N: usize>(
+    ascii_str: &str,
+) -> Result<[Tryte; N], ConversionError> {
+    assert_len_ratio::<N, N, 3, 3>();
+
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; N];
+
+    if ascii_str.is_empty() {
+        return Ok(trytes);
+    }
+
+    check_ascii(ascii_str)?;
+    check_len(ascii_str.len(), N / 3 * 2)?;
+
+    write_from_ascii(ascii_str, &mut trytes);
+    Ok(trytes)
+}
+
+macro_rules! from_ascii_conv {
+    ($func_name:ident, $try_func_name:ident, $length:expr) => {
+        /// Converts an ASCII string to trytes.
+        pub fn $func_name(ascii_str: &str) -> [Tryte; $length] {
+            from_ascii_array(ascii_str)
+        }
+
+        /// Tries to convert an ASCII string to trytes.
+        pub fn $try_func_name(ascii_str: &str) -> Result<[Tryte; $length], ConversionError> {
+            try_from_ascii_array(ascii_str)
+        }
+    };
+}
+
+from_ascii_conv!(from_ascii_18, try_from_ascii_18, 27);
+from_ascii_conv!(from_ascii_6, try_from_ascii_6, 9);
 
-    if ascii.len() % 2 != 0 {
-        ascii.push(0);
+/// Converts an ASCII string to trytes.
+#[cfg(feature = "alloc")]
+pub fn from_ascii(ascii_str: &str) -> Vec<Tryte> {
+    #[cfg(not(feature = "no_checks"))]
+    {
+        check_ascii(ascii_str).unwrap();
     }
 
-    let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; ascii.len() / 2 * 3];
+    let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; ascii_str.len().div_ceil(2) * 3];
+    write_from_ascii(ascii_str, &mut trytes);
+    trytes
+}
+
+/// Tries to convert an ASCII string to trytes.
+#[cfg(feature = "alloc")]
+pub fn try_from_ascii(ascii_str: &str) -> Result<Vec<Tryte>, ConversionError> {
+    check_ascii(ascii_str)?;
+
+    let mut trytes = vec![TRYTE_CODE_TO_ASCII_CODE[0]; ascii_str.len().div_ceil(2) * 3];
+    write_from_ascii(ascii_str, &mut trytes);
+    Ok(trytes)
+}
+
+/// Converts an ASCII string to trytes, writing them into `trytes`.
+///
+/// Returns the number of trytes written.
+pub fn from_ascii_into(ascii_str: &str, trytes: &mut [Tryte]) -> Result<usize, ConversionError> {
+    check_ascii(ascii_str)?;
 
-    let mut tryte_index = 0;
-    for i in (0..(ascii.len() - 1)).step_by(2) {
-        let index = ascii[i] * 127 + ascii[i + 1] - MAX_TRYTE_TRIPLET_ABS;
+    let len = ascii_str.len().div_ceil(2) * 3;
+    check_buf_len(trytes.len(), len)?;
 
-        let tryte_triplet = from_num_i64_to_3(index);
+    write_from_ascii(ascii_str, &mut trytes[..len]);
+    Ok(len)
+}
+
+/// Converts an ASCII string to a `heapless::Vec` of trytes.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_ascii_heapless<const N: usize>(
+    ascii_str: &str,
+) -> Result<heapless::Vec<Tryte, N>, ConversionError> {
+    into_heapless(|buf| from_ascii_into(ascii_str, buf))
+}
 
-        trytes[tryte_index] = tryte_triplet[0];
-        trytes[tryte_index + 1] = tryte_triplet[1];
-        trytes[tryte_index + 2] = tryte_triplet[2];
+#[inline]
+fn write_from_ascii(ascii_str: &str, trytes: &mut [Tryte]) {
+    let ascii = ascii_str.as_bytes();
 
-        tryte_index += 3;
+    for (i, pair) in ascii.chunks(2).enumerate() {
+        trytes[(i * 3)..(i * 3 + 3)].copy_from_slice(&from_ascii_pair(pair));
     }
+}
+
+/// Converts one or two ASCII characters to a tryte triplet.
+#[inline]
+pub(crate) fn from_ascii_pair(pair: &[u8]) -> [Tryte; 3] {
+    let c0 = i64::from(pair[0]);
+    let c1 = if pair.len() == 2 { i64::from(pair[1]) } else { 0 };
 
+    let index = c0 * 127 + c1 - MAX_TRYTE_TRIPLET_ABS;
+
+    from_num_i64_to_3(index)
+}
+
+/// Converts a tryte string to an array of `N` trytes in a `const` context.
+///
+/// Fails to compile if the string is not made of `N` trytes. Used by the `trytes!` macro.
+pub const fn from_tryte_str_const<const N: usize>(tryte_str: &str) -> [Tryte; N] {
+    let bytes = tryte_str.as_bytes();
+    assert!(bytes.len() == N, "tryte string has the wrong length");
+
+    let mut trytes = [TRYTE_CODE_TO_ASCII_CODE[0]; N];
+    let mut i = 0;
+    while i < N {
+        trytes[i] = match Tryte::from_byte(bytes[i]) {
+            Some(tryte) => tryte,
+            None => panic!("invalid tryte in tryte string"),
+        };
+        i += 1;
+    }
     trytes
 }
 
 /// Converts a tryte string to trytes.
+///
+/// The tryte string is checked even with `no_checks`, since every `Tryte` has to be one of `9A-Z`.
+#[cfg(feature = "alloc")]
 pub fn from_tryte_str(tryte_str: &str) -> Vec<Tryte> {
-    tryte_str.as_bytes().to_vec()
+    try_from_tryte_str(tryte_str).unwrap()
+}
+
+/// Tries to convert a tryte string to trytes.
+#[cfg(feature = "alloc")]
+pub fn try_from_tryte_str(tryte_str: &str) -> Result<Vec<Tryte>, ConversionError> {
+    check_tryte_str(tryte_str)?;
+
+    Ok(tryte_str.bytes().map(Tryte).collect())
+}
+
+/// Converts a tryte string to trytes, writing them into `trytes`.
+///
+/// Returns the number of trytes written.
+pub fn from_tryte_str_into(
+    tryte_str: &str,
+    trytes: &mut [Tryte],
+) -> Result<usize, ConversionError> {
+    check_tryte_str(tryte_str)?;
+    check_buf_len(trytes.len(), tryte_str.len())?;
+
+    trytes.iter_mut().zip(tryte_str.bytes()).for_each(|(t, c)| *t = Tryte(c));
+    Ok(tryte_str.len())
+}
+
+/// Converts a tryte string to a `heapless::Vec` of trytes.
+///
+/// Fails if they don't fit into its capacity `N`.
+pub fn from_tryte_str_heapless<const N: usize>(
+    tryte_str: &str,
+) -> Result<heapless::Vec<Tryte, N>, ConversionError> {
+    into_heapless(|buf| from_tryte_str_into(tryte_str, buf))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const TRANSACTION: &str = "SEGQSWYCJHRLJYEGZLRYQAZPLVRAYIWGWJUMFFX99UZUKBQNFYAOQLOFARIKNEBKDRHJJWDJARXTNPHPAODJRSGJBVVYBVJHZALJWDCJHZRSACOVCVVAVHZVTPFTAJWVGFSVLSYXHNNXEGSMJHDBZKGFQNYJJJBAPDHFFGZ9POSOMWTDPGXI9KQRLMUVWNEQDANMXROVORJVALWVGDDJAFOOBXUKVCCIVXSSHZUCZV9XVBASLWX9NXPWGMGYCRD9ILQMKIGPBGGMKAIJKNALBLABATYFVIRBKTXTWNUZAUXRASB9EEIQHWBD9ZYUDBUPBSWXVYXQXECRCHQAYH9ZBUZBASPOIGBSGWJYFKFRITUBVMCYGCMAPTXOIWEVTUXSUOUPTUQOPMMPUTHXMOP9CW9THAZXEPMOMNEOBLUBPOAIOBEBERRZCIKHSTDWUSUPUWNJOCLNZDCEKWWAAJDPJXJEHHSYFN9MH9BGUDQ9CSZBIHRC9PSQJPGKH9ILZDWUWLEKWFKUFFFIMOQKRMKOYXEJHXLCEGCGGKHGJUHOXINSWCKRNMUNAJDCVLZGEBII9ASTYFTDYDZIZSNHIWHSQ9HODQMVNDKMKHCFDXIIGDIVJSBOOE9GRIXCD9ZUTWCUDKFTETSYSRBQABXCXZFOWQMQFXHYZWD9JZXUWHILMRNWXSGUMIIXZYCTWWHCWMSSTCNSQXQXMQPTM9MOQMIVDYNNARDCVNQEDTBKWOIOSKPKPOZHJGJJGNYWQWUWAZMBZJ9XEJMRVRYFQPJ9NOIIXEGIKMMN9DXYQUILRSCSJDIDN9DCTFGQIYWROZQIEQTKMRVLGGDGA9UVZPNRGSVTZYAPMWFUWDEUULSEEGAGITPJQ9DBEYEN9NVJPUWZTOTJHEQIXAPDOICBNNCJVDNM9YRNXMMPCOYHJDUFNCYTZGRCBZKOLHHUK9VOZWHEYQND9WUHDNGFTAS99MRCAU9QOYVUZKTIBDNAAPNEZBQPIRUFUMAWVTCXSXQQIYQPRFDUXCLJNMEIKVAINVCCZROEWEX9XVRM9IHLHQCKC9VLK9ZZWFBJUZKGJCSOPQPFVVAUDLKFJIJKMLZXFBMXLMWRSNDXRMMDLE9VBPUZB9SVLTMHA9DDDANOKIPY9ULDWAKOUDFEDHZDKMU9VMHUSFG9HRGZAZULEJJTEH9SLQDOMZTLVMBCXVNQPNKXRLBOUCCSBZRJCZIUFTFBKFVLKRBPDKLRLZSMMIQNMOZYFBGQFKUJYIJULGMVNFYJWPKPTSMYUHSUEXIPPPPPJTMDQLFFSFJFEPNUBDEDDBPGAOEJGQTHIWISLRDAABO9H9CSIAXPPJYCRFRCIH9TVBZKTCK9SPQZUYMUOKMZYOMPRHRGF9UAKZTZZG9VVVTIHMSNDREUOUOSLKUHTNFXTNSJVPVWCQXUDIMJIAMBPXUGBNDTBYPKYQYJJCDJSCTTWHOJKORLHGKRJMDCMRHSXHHMQBFJWZWHNUHZLYOAFQTRZFXDBYASYKWEVHKYDTJIAUKNCCEPSW9RITZXBOFKBAQOWHKTALQSCHARLUUGXISDMBVEUKOVXTKTEVKLGYVYHPNYWKNLCVETWIHHVTBWT9UPMTQWBZPRPRSISUBIBECVDNIZQULAGLONGVFLVZPBMHJND9CEVIXSYGFZAGGN9MQYOAKMENSEOGCUNKEJTDLEDCD9LGKYANHMZFSSDDZJKTKUJSFL9GYFDICTPJEPDSBXDQTARJQEWUVWDWSQPKIHPJONKHESSQH9FNQEO9WUCFDWPPPTIQPWCVDYTTWPLCJJVYNKE9ZEJNQBEJBMDBLNJKQDOQOHVS9VY9UPSU9KZVDFOESHNRRWBK9EZCYALAUYFGPCEWJQDXFENSNQEAUWDXJGOMCLQUQWMCPHOBZZ9SZJ9KZXSHDLPHPNYMVUJQSQETTN9SG9SIANJHWUYQXZXAJLYHCZYRGITZYQLAAYDVQVNKCDIYWAYBAFBMAYEAEAGMTJGJRSNHBHCEVIQRXEFVWJWOPU9FPDOWIFL9EWGHICRBNRITJDZNYACOGTUDBZYIYZZWAOCDBQFFNTTSTGKECWTVWZSPHX9HNRUYEAEWXENEIDLVVFMZFVPUNHMQPAIOKVIBDIHQIHFGRJOHHONPLGBSJUD9HHDTQQUZN9NVJYOAUMXMMOCNUFLZ9MXKZAGDGKVADXOVCAXEQYZGOGQKDLKIUPYXIL9PXYBQXGYDEGNXTFURSWQYLJDFKEV9VVBBQLTLHIBTFYBAJSZMDMPQHPWSFVWOJQDPHV9DYSQPIBL9LYZHQKKOVF9TFVTTXQEUWFQSLGLVTGK99VSUEDXIBIWCQHDQQSQLDHZ9999999999999999999TRINITY99999999999999999999TNXSQ9D99A99999999B99999999OGBHPUUHS9CKWSAPIMDIRNSUJ9CFPGKTUFAGQYVMFKOZSVAHIFJXWCFBZLICUWF9GNDZWCOWDUIIZ9999OXNRVXLBKJXEZMVABR9UQBVSTBDFSAJVRRNFEJRL9UFTOFPJHQMQKAJHDBIQAETS9OUVTQ9DSPAOZ9999TRINITY99999999999999999999LPZYMWQME999999999MMMMMMMMMDTIZE9999999999999999999999";
+    use crate::{error::ErrorKind, test_vectors::TRANSACTION};
 
     #[test]
     fn test_from_bytes() {
@@ -288,96 +636,66 @@ mod tests {
 
         let bytes = crate::bytes::from_trytes(&from_tryte_str(TRANSACTION));
 
-        assert_eq!(
-            &bytes[..],
-            &crate::bytes::from_trytes(&from_bytes(&bytes))[..]
-        );
+        assert_eq!(&bytes[..], &crate::bytes::from_trytes(&from_bytes(&bytes))[..]);
     }
 
     #[test]
     fn test_from_trits_all() {
         let all_trits = &crate::trits::from_tryte_str(TRANSACTION)[..];
 
-        assert_eq!(
-            &all_trits[..],
-            &crate::trits::from_trytes(&from_trits_all(&all_trits))[..]
-        );
+        assert_eq!(all_trits, &crate::trits::from_trytes(&from_trits_all(all_trits))[..]);
 
         let sig_trits = &crate::trits::from_tryte_str(TRANSACTION)[0..6561];
 
-        assert_eq!(
-            &sig_trits[..],
-            &crate::trits::from_trytes(&from_trits_sig(&sig_trits))[..]
-        );
+        assert_eq!(sig_trits, &crate::trits::from_trytes(&from_trits_sig(sig_trits))[..]);
     }
 
     #[test]
     fn test_from_trits_27() {
-        let trits = [
+        let trits = crate::trits::from_raw(&[
             1, 1, 0, -1, -1, 0, -1, 1, 0, 1, -1, 0, 1, 1, 1, 1, -1, -1, 1, -1, 0, 1, -1, 0, 1, -1,
             0,
-        ];
+        ]);
 
-        assert_eq!(
-            &trits,
-            &crate::trits::from_trytes(&from_trits_27(&trits))[..]
-        );
+        assert_eq!(trits, crate::trits::from_trytes(&from_trits_27(&trits)));
     }
 
     #[test]
     fn test_from_trits() {
-        assert_eq!(
-            &[1, 0, 0],
-            &crate::trits::from_trytes(&from_trits(&[1, 0, 0]))[..]
-        );
-        assert_eq!(
-            &[1, -1, 0, -1, 1, 0],
-            &crate::trits::from_trytes(&from_trits(&[1, -1, 0, -1, 1, 0]))[..]
-        );
+        let trits = crate::trits::from_raw(&[1, 0, 0]);
+        assert_eq!(trits, crate::trits::from_trytes(&from_trits(&trits)));
+
+        let trits = crate::trits::from_raw(&[1, -1, 0, -1, 1, 0]);
+        assert_eq!(trits, crate::trits::from_trytes(&from_trits(&trits)));
     }
 
     #[test]
     fn test_from_num_i64() {
-        assert_eq!(
-            729,
-            crate::numbers::from_trytes_max13(&from_num_i64(729)[..])
-        );
+        assert_eq!(729, crate::numbers::from_trytes_max13(&from_num_i64(729)[..]));
 
         assert_eq!(0, crate::numbers::from_trytes_max13(&from_num_i64(0)[..]));
 
-        assert_eq!(
-            1234567890,
-            crate::numbers::from_trytes_max13(&from_num_i64(1234567890)[..])
-        );
+        assert_eq!(1234567890, crate::numbers::from_trytes_max13(&from_num_i64(1234567890)[..]));
 
         assert_eq!(
-            core::i64::MAX / 8,
-            crate::numbers::from_trytes_max13(&from_num_i64(core::i64::MAX / 8)[..])
+            i64::MAX / 8,
+            crate::numbers::from_trytes_max13(&from_num_i64(i64::MAX / 8)[..])
         );
     }
 
     #[test]
     fn test_from_num_i64_to_13() {
-        assert_eq!(
-            729,
-            crate::numbers::from_trytes_max13(&from_num_i64_to_13(729)[..])
-        );
+        assert_eq!(729, crate::numbers::from_trytes_max13(&from_num_i64_to_13(729)[..]));
     }
 
     #[test]
     fn test_from_num_i64_to_11() {
-        assert_eq!(
-            729,
-            crate::numbers::from_trytes_max11(&from_num_i64_to_11(729)[..])
-        );
+        assert_eq!(729, crate::numbers::from_trytes_max11(&from_num_i64_to_11(729)[..]));
     }
 
     #[test]
     fn test_from_ascii_6() {
-        assert_eq!(
-            "Hello!",
-            crate::ascii_strings::from_trytes(&from_ascii_6("Hello!")[..])
-        );
+        assert_eq!("Hello!", crate::ascii_strings::from_trytes(&from_ascii_6("Hello!")[..]));
     }
 
     #[test]
@@ -388,15 +706,201 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_array() {
+        let trits: [Trit; 243] = crate::trits::from_tryte_str_81(&TRANSACTION[..81]);
+        let trytes: [Tryte; 81] = from_trits_array(&trits);
+        assert_eq!(from_tryte_str(&TRANSACTION[..81]), trytes);
+
+        let bytes: [u8; 54] = crate::bytes::from_trytes_array(&trytes);
+        let trytes: [Tryte; 81] = from_bytes_array(&bytes);
+        assert_eq!(from_tryte_str(&TRANSACTION[..81]), trytes);
+
+        let trytes: [Tryte; 5] = from_num_i64_array(729);
+        assert_eq!(729, crate::numbers::from_trytes_max13(&trytes));
+        assert_eq!(ErrorKind::OutOfRange, try_from_num_i64_array::<1>(14).unwrap_err().kind());
+
+        // 13 trytes don't cover the range of `i64`, 14 do.
+        assert!(try_from_num_i64_array::<13>(i64::MAX).is_err());
+        let trytes = try_from_num_i64_array::<14>(i64::MAX).unwrap();
+        assert_eq!(from_num_i64(i64::MAX), trytes);
+        let trytes = try_from_num_i64_array::<14>(i64::MIN).unwrap();
+        assert_eq!(from_i128(i128::from(i64::MIN)), trytes);
+        assert_eq!(from_num_i64(i64::MIN), from_num_i64_array::<20>(i64::MIN)[..14]);
+
+        let trytes: [Tryte; 3] = from_ascii_array("Hi");
+        assert_eq!("Hi", crate::ascii_strings::from_trytes(&trytes));
+    }
+
+    #[test]
+    fn test_try_from_trits() {
+        let trits = crate::trits::from_raw(&[1, 0, 0, 0, 0, 0]);
+        assert_eq!(from_tryte_str("A9"), try_from_trits(&trits).unwrap());
+
+        let err = try_from_trits(&trits[..4]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidLength, err.kind());
+        assert_eq!(4, err.index());
+    }
+
+    #[test]
+    fn test_into() {
+        let mut trytes = [Tryte::default(); 12];
+
+        assert_eq!(Ok(3), from_bytes_into(&[12, 34], &mut trytes));
+        assert_eq!(from_bytes(&[12, 34]), trytes[..3]);
+
+        let trits = crate::trits::from_raw(&[1, -1, 0, -1, 1, 0]);
+        assert_eq!(Ok(2), from_trits_into(&trits, &mut trytes));
+        assert_eq!(from_trits(&trits), trytes[..2]);
+
+        assert_eq!(Ok(3), from_num_i64_into(-729, &mut trytes));
+        assert_eq!(from_num_i64(-729), trytes[..3]);
+
+        let mut trytes_14 = [Tryte::default(); 20];
+        assert_eq!(Ok(14), from_num_i64_into(i64::MIN, &mut trytes_14));
+        assert_eq!(from_i128(i128::from(i64::MIN)), trytes_14[..14]);
+        assert_eq!(Ok(14), from_num_i64_into(i64::MAX, &mut trytes_14));
+        assert_eq!(from_i128(i128::from(i64::MAX)), trytes_14[..14]);
+        assert_eq!(
+            from_i128(i128::from(i64::MIN)),
+            &from_num_i64_heapless::<14>(i64::MIN).unwrap()[..]
+        );
+
+        assert_eq!(Ok(9), from_ascii_into("Hello", &mut trytes));
+        assert_eq!(from_ascii("Hello"), trytes[..9]);
+
+        assert_eq!(Ok(4), from_tryte_str_into("SEG9", &mut trytes));
+        assert_eq!(from_tryte_str("SEG9"), trytes[..4]);
+
+        let err = from_ascii_into("Hello, IOTA!", &mut trytes).unwrap_err();
+        assert_eq!(ErrorKind::BufferTooSmall, err.kind());
+        assert_eq!(18, err.index());
+    }
+
+    #[test]
+    fn test_heapless() {
+        let trytes = from_ascii_heapless::<9>("Hello").unwrap();
+        assert_eq!(from_ascii("Hello"), &trytes[..]);
+
+        let trytes = from_num_i64_heapless::<2>(27).unwrap();
+        assert_eq!(from_num_i64(27), &trytes[..]);
+
+        let err = from_num_i64_heapless::<2>(729).unwrap_err();
+        assert_eq!(ErrorKind::BufferTooSmall, err.kind());
+    }
+
+    #[test]
+    fn test_num_i64_len() {
+        assert_eq!(0, num_i64_len(0));
+        assert_eq!(1, num_i64_len(13));
+        assert_eq!(2, num_i64_len(-14));
+        assert_eq!(3, num_i64_len(9841));
+        assert_eq!(4, num_i64_len(9842));
+        assert_eq!(14, num_i64_len(i64::MIN));
+    }
+
+    #[test]
+    fn test_try_from_num_i64_to_3() {
+        assert_eq!(9841, crate::numbers::from_trytes_max11(&try_from_num_i64_to_3(9841).unwrap()));
+        assert_eq!(ErrorKind::OutOfRange, try_from_num_i64_to_3(9842).unwrap_err().kind());
+        assert_eq!(ErrorKind::OutOfRange, try_from_num_i64_to_3(-9842).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_try_from_ascii() {
+        assert_eq!(from_ascii("Hello"), try_from_ascii("Hello").unwrap());
+
+        let err = try_from_ascii("Grüße").unwrap_err();
+        assert_eq!(ErrorKind::NonAscii, err.kind());
+        assert_eq!(2, err.index());
+    }
+
+    #[test]
+    fn test_try_from_tryte_str() {
+        assert_eq!(Ok(vec![Tryte(b'A'), Tryte(b'B'), Tryte(b'9')]), try_from_tryte_str("AB9"));
+
+        let err = try_from_tryte_str("ABC0").unwrap_err();
+        assert_eq!(ErrorKind::InvalidTryte, err.kind());
+        assert_eq!(3, err.index());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_tryte_str_invalid() {
+        // Even with `no_checks`, no invalid `Tryte` is built.
+        let _ = from_tryte_str("ABC0");
+    }
+
     #[test]
     fn test_from_ascii() {
         assert_eq!(
             "Hello, World!",
             crate::ascii_strings::from_trytes(&from_ascii("Hello, World!")[..])
         );
-        assert_eq!(
-            "Hel",
-            crate::ascii_strings::from_trytes(&from_ascii("Hel")[..])
-        );
+        assert_eq!("Hel", crate::ascii_strings::from_trytes(&from_ascii("Hel")[..]));
+    }
+
+    #[test]
+    fn test_from_s129() {
+        use crate::{numbers::from_trytes_max27, types::Sign};
+
+        assert_eq!(from_tryte_str("9"), from_s129_to_27(S129::ZERO)[..1]);
+        assert!(from_s129(S129::ZERO).is_empty());
+        assert_eq!(from_tryte_str("A"), from_i128(1));
+        assert_eq!(from_tryte_str("N"), from_i128(-13));
+        assert_eq!(from_tryte_str("NA"), from_i128(14));
+        assert_eq!(from_num_i64(i64::MAX), from_i128(i128::from(i64::MAX)));
+
+        let max = S129(Sign::Pos, 221713244121518884974124815309574946401);
+        assert_eq!([Tryte(b'M'); 27], from_s129_to_27(max));
+        assert_eq!([Tryte(b'N'); 27], from_s129_to_27(-max));
+        assert_eq!(max, from_trytes_max27(&from_s129(max)));
+
+        let too_large = max + S129::from(1_i64);
+        assert_eq!(ErrorKind::OutOfRange, try_from_s129_to_27(too_large).unwrap_err().kind());
+        assert_eq!(ErrorKind::OutOfRange, try_from_s129(-too_large).unwrap_err().kind());
+
+        let mut trytes = [Tryte::default(); 2];
+        assert_eq!(Ok(2), from_s129_into(S129::from(14_i64), &mut trytes));
+        let err = from_s129_into(S129::from(400_i64), &mut trytes).unwrap_err();
+        assert_eq!(ErrorKind::BufferTooSmall, err.kind());
+        assert_eq!(3, err.index());
+    }
+
+    #[test]
+    fn test_from_s129_roundtrip() {
+        use crate::{numbers::from_trytes_max27, types::Sign};
+        use rand::{thread_rng, Rng};
+
+        let mut rng = thread_rng();
+
+        for _ in 0..1000 {
+            // Spread the magnitudes over all lengths instead of mostly hitting 27 trytes.
+            let magnitude = rng.gen::<u128>() >> rng.gen_range(0, 128) >> 1;
+            let sign = if rng.gen() { Sign::Pos } else { Sign::Neg };
+            let number = S129::new(sign, magnitude);
+
+            let trytes = from_s129(number);
+            assert_eq!(number, from_trytes_max27(&trytes));
+            assert_eq!(number, from_trytes_max27(&from_s129_to_27(number)));
+            assert_ne!(Some(&TRYTE_CODE_TO_ASCII_CODE[0]), trytes.last());
+
+            let trits = crate::trits::from_s129(number);
+            assert_eq!(
+                number,
+                from_trytes_max27(&from_trits(&crate::trits::from_s129_to_81(number)))
+            );
+            assert_ne!(Some(&Trit::Zero), trits.last());
+            assert_eq!(trytes.len(), trits.len().div_ceil(3));
+        }
+
+        for _ in 0..1000 {
+            let number = rng.gen::<i128>();
+            assert_eq!(S129::from(number), from_trytes_max27(&from_i128_to_27(number)));
+            assert_eq!(
+                crate::trits::from_i128_to_81(number),
+                crate::trits::from_s129_to_81(number.into())
+            );
+        }
     }
 }
@@ -1,15 +1,125 @@
-//!  Meaningful type aliases and a signed 129 bit integer to store values at least up to (3^81-1)/2.
+//! Trit and tryte types, meaningful type aliases and a signed 129 bit integer to store values at
+//! least up to (3^81-1)/2.
 
-use std::ops::AddAssign;
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::error::{ConversionError, ErrorKind};
 
 /// Representation of an unsigned byte.
 pub type Byte = u8;
 
-/// Representation of a trit.
-pub type Trit = i8;
+/// A balanced trit, i.e. one of `-1`, `0` or `1`.
+#[repr(i8)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Trit {
+    /// The trit `-1`.
+    Neg = -1,
+    /// The trit `0`.
+    #[default]
+    Zero = 0,
+    /// The trit `1`.
+    Pos = 1,
+}
+
+impl TryFrom<i8> for Trit {
+    type Error = ConversionError;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            -1 => Ok(Trit::Neg),
+            0 => Ok(Trit::Zero),
+            1 => Ok(Trit::Pos),
+            _ => Err(ConversionError::new(ErrorKind::InvalidTrit, 0)),
+        }
+    }
+}
+
+impl From<Trit> for i8 {
+    fn from(trit: Trit) -> Self {
+        trit as i8
+    }
+}
+
+/// A tryte, i.e. one of the ASCII characters `9A-Z`.
+// Transparent, so the SIMD kernels can read and write trytes as bytes.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Tryte(pub(crate) u8);
+
+impl Tryte {
+    /// Returns the tryte with the given ASCII code, or `None` if it's not one of `9A-Z`.
+    ///
+    /// Unlike `TryFrom<u8>` this can be used in `const` contexts.
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'9' | b'A'..=b'Z' => Some(Tryte(byte)),
+            _ => None,
+        }
+    }
+
+    /// Returns the ASCII code of this tryte.
+    pub const fn as_byte(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the character of this tryte.
+    pub const fn as_char(self) -> char {
+        self.0 as char
+    }
+}
+
+impl Default for Tryte {
+    fn default() -> Self {
+        Tryte(b'9')
+    }
+}
+
+impl TryFrom<u8> for Tryte {
+    type Error = ConversionError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            b'9' | b'A'..=b'Z' => Ok(Tryte(value)),
+            _ => Err(ConversionError::new(ErrorKind::InvalidTryte, 0)),
+        }
+    }
+}
 
-/// Representation of a tryte.
-pub type Tryte = u8;
+impl TryFrom<char> for Tryte {
+    type Error = ConversionError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '9' | 'A'..='Z' => Ok(Tryte(value as u8)),
+            _ => Err(ConversionError::new(ErrorKind::InvalidTryte, 0)),
+        }
+    }
+}
+
+impl From<Tryte> for u8 {
+    fn from(tryte: Tryte) -> Self {
+        tryte.0
+    }
+}
+
+impl From<Tryte> for char {
+    fn from(tryte: Tryte) -> Self {
+        tryte.as_char()
+    }
+}
+
+impl fmt::Display for Tryte {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
 
 /// A Sign for the `S129` signed 129 bit integer.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -21,37 +131,274 @@ pub enum Sign {
 }
 
 /// A signed 129 bit integer.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+///
+/// Zero has two representations, `S129(Sign::Pos, 0)` and `S129(Sign::Neg, 0)`, which compare
+/// equal. All operations return the canonical `S129(Sign::Pos, 0)`.
+///
+/// The arithmetic operators panic on overflow, the `checked_*` methods return `None` instead.
+#[derive(Copy, Clone, Debug)]
 pub struct S129(pub Sign, pub u128);
 
+impl S129 {
+    /// The value `0`.
+    pub const ZERO: S129 = S129(Sign::Pos, 0);
+    /// The largest value, `2^128 - 1`.
+    pub const MAX: S129 = S129(Sign::Pos, u128::MAX);
+    /// The smallest value, `-(2^128 - 1)`.
+    pub const MIN: S129 = S129(Sign::Neg, u128::MAX);
+
+    /// Creates a number from its sign and magnitude, canonicalising zero.
+    pub const fn new(sign: Sign, magnitude: u128) -> S129 {
+        if magnitude == 0 {
+            S129::ZERO
+        } else {
+            S129(sign, magnitude)
+        }
+    }
+
+    /// Returns whether the number is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.1 == 0
+    }
+
+    /// Returns whether the number is less than zero.
+    pub const fn is_negative(&self) -> bool {
+        matches!(self.0, Sign::Neg) && self.1 != 0
+    }
+
+    /// Returns the number with a canonical zero.
+    pub const fn canonical(self) -> S129 {
+        S129::new(self.0, self.1)
+    }
+
+    /// Adds two numbers, returning `None` on overflow.
+    pub fn checked_add(self, other: S129) -> Option<S129> {
+        if self.0 == other.0 {
+            self.1.checked_add(other.1).map(|m| S129::new(self.0, m))
+        } else if self.1 >= other.1 {
+            Some(S129::new(self.0, self.1 - other.1))
+        } else {
+            Some(S129::new(other.0, other.1 - self.1))
+        }
+    }
+
+    /// Subtracts `other` from the number, returning `None` on overflow.
+    pub fn checked_sub(self, other: S129) -> Option<S129> {
+        self.checked_add(-other)
+    }
+
+    /// Multiplies two numbers, returning `None` on overflow.
+    pub fn checked_mul(self, other: S129) -> Option<S129> {
+        let sign = if self.0 == other.0 { Sign::Pos } else { Sign::Neg };
+
+        self.1.checked_mul(other.1).map(|m| S129::new(sign, m))
+    }
+}
+
+impl Default for S129 {
+    fn default() -> Self {
+        S129::ZERO
+    }
+}
+
+impl PartialEq for S129 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for S129 {}
+
+impl Hash for S129 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let canonical = self.canonical();
+        canonical.is_negative().hash(state);
+        canonical.1.hash(state);
+    }
+}
+
+impl PartialOrd for S129 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for S129 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (false, false) => self.1.cmp(&other.1),
+            (true, true) => other.1.cmp(&self.1),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl Neg for S129 {
+    type Output = S129;
+
+    fn neg(self) -> S129 {
+        let sign = match self.0 {
+            Sign::Pos => Sign::Neg,
+            Sign::Neg => Sign::Pos,
+        };
+        S129::new(sign, self.1)
+    }
+}
+
+impl Add for S129 {
+    type Output = S129;
+
+    fn add(self, other: S129) -> S129 {
+        self.checked_add(other).expect("attempt to add with overflow")
+    }
+}
+
+impl Sub for S129 {
+    type Output = S129;
+
+    fn sub(self, other: S129) -> S129 {
+        self.checked_sub(other).expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for S129 {
+    type Output = S129;
+
+    fn mul(self, other: S129) -> S129 {
+        self.checked_mul(other).expect("attempt to multiply with overflow")
+    }
+}
+
 impl AddAssign for S129 {
     fn add_assign(&mut self, other: Self) {
-        match (&self.0, other.0) {
-            (Sign::Pos, Sign::Neg) => {
-                if other.1 > self.1 {
-                    self.0 = Sign::Neg;
-                    self.1 = other.1 - self.1;
-                } else {
-                    self.1 -= other.1;
-                }
-            }
-            (Sign::Neg, Sign::Pos) => {
-                if other.1 >= self.1 {
-                    self.0 = Sign::Pos;
-                    self.1 = other.1 - self.1;
-                } else {
-                    self.1 -= other.1;
-                }
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for S129 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for S129 {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl From<i64> for S129 {
+    fn from(value: i64) -> Self {
+        S129::from(i128::from(value))
+    }
+}
+
+impl From<i128> for S129 {
+    fn from(value: i128) -> Self {
+        let sign = if value < 0 { Sign::Neg } else { Sign::Pos };
+        S129::new(sign, value.unsigned_abs())
+    }
+}
+
+impl From<u128> for S129 {
+    fn from(value: u128) -> Self {
+        S129::new(Sign::Pos, value)
+    }
+}
+
+impl TryFrom<S129> for i128 {
+    type Error = ConversionError;
+
+    fn try_from(value: S129) -> Result<Self, Self::Error> {
+        if value.is_negative() {
+            // -2^127 is the only value whose magnitude doesn't fit into a positive i128.
+            0_i128.checked_sub_unsigned(value.1)
+        } else {
+            i128::try_from(value.1).ok()
+        }
+        .ok_or_else(|| ConversionError::new(ErrorKind::OutOfRange, 0))
+    }
+}
+
+impl TryFrom<S129> for i64 {
+    type Error = ConversionError;
+
+    fn try_from(value: S129) -> Result<Self, Self::Error> {
+        i64::try_from(i128::try_from(value)?)
+            .map_err(|_| ConversionError::new(ErrorKind::OutOfRange, 0))
+    }
+}
+
+impl fmt::Display for S129 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_negative() {
+            write!(f, "-{}", self.1)
+        } else {
+            write!(f, "{}", self.1)
+        }
+    }
+}
+
+impl FromStr for S129 {
+    type Err = ConversionError;
+
+    /// Parses an optionally signed decimal number like `-123` or `+45`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (sign, digits, offset) = match s.as_bytes().first() {
+            Some(b'-') => (Sign::Neg, &s[1..], 1),
+            Some(b'+') => (Sign::Pos, &s[1..], 1),
+            _ => (Sign::Pos, s, 0),
+        };
+
+        if digits.is_empty() {
+            return Err(ConversionError::new(ErrorKind::InvalidLength, s.len()));
+        }
+
+        let mut magnitude = 0_u128;
+        for (i, c) in digits.bytes().enumerate() {
+            if !c.is_ascii_digit() {
+                return Err(ConversionError::new(ErrorKind::InvalidDigit, offset + i));
             }
-            (_, _) => self.1 += other.1,
+
+            magnitude = magnitude
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(u128::from(c - b'0')))
+                .ok_or_else(|| ConversionError::new(ErrorKind::OutOfRange, offset + i))?;
         }
+
+        Ok(S129::new(sign, magnitude))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::string::ToString;
+
     use super::*;
 
+    #[test]
+    fn test_trit_conversions() {
+        assert_eq!(Ok(Trit::Neg), Trit::try_from(-1));
+        assert_eq!(Ok(Trit::Zero), Trit::try_from(0));
+        assert_eq!(Ok(Trit::Pos), Trit::try_from(1));
+        assert_eq!(ErrorKind::InvalidTrit, Trit::try_from(5).unwrap_err().kind());
+        assert_eq!(-1, i8::from(Trit::Neg));
+        assert_eq!(1, Trit::Pos as i8);
+    }
+
+    #[test]
+    fn test_tryte_conversions() {
+        assert_eq!(Ok(Tryte(b'9')), Tryte::try_from(b'9'));
+        assert_eq!(Ok(Tryte(b'M')), Tryte::try_from('M'));
+        assert_eq!(ErrorKind::InvalidTryte, Tryte::try_from(b'a').unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidTryte, Tryte::try_from('@').unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidTryte, Tryte::try_from('[').unwrap_err().kind());
+        assert_eq!(b'Z', u8::from(Tryte(b'Z')));
+        assert_eq!('Z', char::from(Tryte(b'Z')));
+    }
+
     #[test]
     fn test_s129_add_assign() {
         // Example: (+, 2) + (-, 4) = (-, 2)
@@ -102,4 +449,101 @@ mod tests {
         a += b;
         assert_eq!(S129(Sign::Neg, 6), a);
     }
+
+    #[test]
+    fn test_s129_zero() {
+        assert_eq!(S129(Sign::Neg, 0), S129::ZERO);
+        assert_eq!(S129::ZERO, S129::new(Sign::Neg, 0));
+        assert!(!S129(Sign::Neg, 0).is_negative());
+        assert_eq!(Sign::Pos, (-S129::ZERO).0);
+        assert_eq!(Sign::Pos, (S129(Sign::Neg, 3) + S129(Sign::Pos, 3)).0);
+        assert_eq!(Sign::Pos, (S129(Sign::Neg, 3) * S129::ZERO).0);
+    }
+
+    #[test]
+    fn test_s129_arithmetic() {
+        let a = S129::from(-7_i64);
+        let b = S129::from(3_i64);
+
+        assert_eq!(S129::from(-10_i64), a - b);
+        assert_eq!(S129::from(10_i64), b - a);
+        assert_eq!(S129::from(7_i64), -a);
+        assert_eq!(S129::from(-21_i64), a * b);
+        assert_eq!(S129::from(49_i64), a * a);
+
+        let mut c = a;
+        c -= b;
+        c *= b;
+        assert_eq!(S129::from(-30_i64), c);
+    }
+
+    #[test]
+    fn test_s129_overflow() {
+        assert_eq!(None, S129::MAX.checked_add(S129::from(1_i64)));
+        assert_eq!(None, S129::MIN.checked_sub(S129::from(1_i64)));
+        assert_eq!(None, S129::MAX.checked_mul(S129::from(-2_i64)));
+        assert_eq!(Some(S129::ZERO), S129::MAX.checked_add(S129::MIN));
+        assert_eq!(Some(S129::MIN), S129::MAX.checked_mul(S129::from(-1_i64)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_s129_add_overflow() {
+        let _ = S129::MAX + S129::from(1_i64);
+    }
+
+    #[test]
+    fn test_s129_ord() {
+        let mut numbers = [
+            S129::MAX,
+            S129::from(-1_i64),
+            S129(Sign::Neg, 0),
+            S129::MIN,
+            S129::from(1_i64),
+            S129::from(-2_i64),
+        ];
+        numbers.sort();
+
+        assert_eq!(
+            [
+                S129::MIN,
+                S129::from(-2_i64),
+                S129::from(-1_i64),
+                S129::ZERO,
+                S129::from(1_i64),
+                S129::MAX
+            ],
+            numbers
+        );
+    }
+
+    #[test]
+    fn test_s129_display_from_str() {
+        assert_eq!("-123", S129::from(-123_i64).to_string());
+        assert_eq!("0", S129(Sign::Neg, 0).to_string());
+        assert_eq!(S129::MIN, S129::MIN.to_string().parse().unwrap());
+        assert_eq!(Ok(S129::from(45_i64)), "+45".parse());
+        assert_eq!(Ok(S129::ZERO), "-0".parse());
+
+        let err = "12a".parse::<S129>().unwrap_err();
+        assert_eq!(ErrorKind::InvalidDigit, err.kind());
+        assert_eq!(2, err.index());
+
+        assert_eq!(ErrorKind::InvalidLength, "-".parse::<S129>().unwrap_err().kind());
+        let too_large = "340282366920938463463374607431768211456";
+        assert_eq!(ErrorKind::OutOfRange, too_large.parse::<S129>().unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_s129_int_conversions() {
+        assert_eq!(Ok(i128::MIN), i128::try_from(S129::from(i128::MIN)));
+        assert_eq!(Ok(i128::MAX), i128::try_from(S129::from(i128::MAX)));
+        assert_eq!(Ok(i64::MIN), i64::try_from(S129::from(i64::MIN)));
+        assert_eq!(Ok(-5), i64::try_from(S129::from(-5_i64)));
+
+        let err = i128::try_from(S129::from(i128::MIN) - S129::from(1_i64)).unwrap_err();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert!(i128::try_from(S129::MAX).is_err());
+        assert!(i64::try_from(S129::from(i128::from(i64::MAX) + 1)).is_err());
+    }
 }
@@ -1,51 +1,172 @@
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::string::String;
 
+use core::convert::TryInto;
+
+#[cfg(feature = "alloc")]
 use crate::luts::TRYTE_CODE_TO_ASCII_CODE;
+use crate::{error::ConversionError, error::ErrorKind};
+
+/// Holds the length relationship `M == N / DEN * NUM` between two arrays.
+///
+/// `N` must be a multiple of `DEN`. Evaluating `HOLDS` fails the build if the relationship is
+/// violated, which turns a mismatched array length into a compile time error.
+pub(crate) struct LenRatio<const N: usize, const M: usize, const NUM: usize, const DEN: usize>;
+
+impl<const N: usize, const M: usize, const NUM: usize, const DEN: usize> LenRatio<N, M, NUM, DEN> {
+    pub(crate) const HOLDS: () = assert!(
+        N.is_multiple_of(DEN) && N / DEN * NUM == M,
+        "array lengths don't fit the conversion"
+    );
+}
+
+/// Asserts at compile time that `M == N / DEN * NUM` (see `LenRatio`).
+#[inline(always)]
+pub(crate) fn assert_len_ratio<
+    const N: usize,
+    const M: usize,
+    const NUM: usize,
+    const DEN: usize,
+>() {
+    LenRatio::<N, M, NUM, DEN>::HOLDS
+}
+
+/// Holds the length relationship `M == ceil(N / PER)` between an array of `N` trits and the array
+/// of `M` bytes that packs them `PER` trits per byte.
+pub(crate) struct PackedLen<const N: usize, const M: usize, const PER: usize>;
+
+impl<const N: usize, const M: usize, const PER: usize> PackedLen<N, M, PER> {
+    pub(crate) const HOLDS: () =
+        assert!(N.div_ceil(PER) == M, "array lengths don't fit the packing");
+}
+
+/// Asserts at compile time that `M == ceil(N / PER)` (see `PackedLen`).
+#[inline(always)]
+pub(crate) fn assert_packed_len<const N: usize, const M: usize, const PER: usize>() {
+    PackedLen::<N, M, PER>::HOLDS
+}
 
-#[cfg(not(feature = "no_checks"))]
-use crate::{
-    types::Trit,
-    types::Tryte,
-};
+/// Borrows a slice as an array of length `N`.
+pub(crate) fn as_array<T, const N: usize>(slice: &[T]) -> Result<&[T; N], ConversionError> {
+    slice.try_into().map_err(|_| ConversionError::new(ErrorKind::InvalidLength, slice.len()))
+}
 
-#[cfg(not(feature = "no_checks"))]
 pub(crate) fn is_tryte_str(tryte_str: &str) -> bool {
-    tryte_str
-        .chars()
-        .find(|c| *c != '9' && (*c < 'A' || *c > 'Z'))
-        .is_none()
+    tryte_str.chars().find(|c| *c != '9' && (*c < 'A' || *c > 'Z')).is_none()
+}
+
+pub(crate) fn check_tryte_str(tryte_str: &str) -> Result<(), ConversionError> {
+    check_trytes(tryte_str.as_bytes())
+}
+
+pub(crate) fn check_trytes(trytes: &[u8]) -> Result<(), ConversionError> {
+    match trytes.iter().position(|t| *t != 57 && (*t < 65 || *t > 90)) {
+        Some(index) => Err(ConversionError::new(ErrorKind::InvalidTryte, index)),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn check_trits(trits: &[i8]) -> Result<(), ConversionError> {
+    match trits.iter().position(|t| !(*t == -1 || *t == 0 || *t == 1)) {
+        Some(index) => Err(ConversionError::new(ErrorKind::InvalidTrit, index)),
+        None => Ok(()),
+    }
+}
+
+/// Checks that every pair of bytes encodes three valid tryte codes in the 9/2 encoding.
+pub(crate) fn check_bytes(bytes: &[u8]) -> Result<(), ConversionError> {
+    for (i, pair) in bytes.chunks_exact(2).enumerate() {
+        let b0 = pair[0] as usize;
+        let b1 = pair[1] as usize;
+
+        if b0 / 8 > 26 || b0 % 8 + 8 * (b1 % 8) > 26 {
+            return Err(ConversionError::new(ErrorKind::OutOfRange, 2 * i));
+        }
+        if b1 / 8 > 26 {
+            return Err(ConversionError::new(ErrorKind::OutOfRange, 2 * i + 1));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn check_ascii(ascii_str: &str) -> Result<(), ConversionError> {
+    match ascii_str.bytes().position(|b| !b.is_ascii()) {
+        Some(index) => Err(ConversionError::new(ErrorKind::NonAscii, index)),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn check_len(len: usize, expected: usize) -> Result<(), ConversionError> {
+    if len != expected {
+        return Err(ConversionError::new(ErrorKind::InvalidLength, len));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_max_len(len: usize, max: usize) -> Result<(), ConversionError> {
+    if len > max {
+        return Err(ConversionError::new(ErrorKind::InvalidLength, len));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_len_multiple_of(len: usize, factor: usize) -> Result<(), ConversionError> {
+    if !len.is_multiple_of(factor) {
+        return Err(ConversionError::new(ErrorKind::InvalidLength, len));
+    }
+    Ok(())
 }
 
-#[cfg(not(feature = "no_checks"))]
-pub(crate) fn is_trytes(trytes: &[Tryte]) -> bool {
-    trytes
-        .iter()
-        .find(|t| **t != 57 && (**t < 65 || **t > 90))
-        .is_none()
+pub(crate) fn check_buf_len(len: usize, required: usize) -> Result<(), ConversionError> {
+    if len < required {
+        return Err(ConversionError::new(ErrorKind::BufferTooSmall, required));
+    }
+    Ok(())
 }
 
-#[cfg(not(feature = "no_checks"))]
-pub(crate) fn is_trits(trits: &[Trit]) -> bool {
-    trits
-        .iter()
-        .find(|t| !(**t == -1 || **t == 0 || **t == 1))
-        .is_none()
+/// Runs an `*_into` converter on a `heapless::Vec` filled up to its capacity, and truncates it to
+/// the number of elements written.
+pub(crate) fn into_heapless<T, F, const N: usize>(
+    convert: F,
+) -> Result<heapless::Vec<T, N>, ConversionError>
+where
+    T: Clone + Default,
+    F: FnOnce(&mut [T]) -> Result<usize, ConversionError>,
+{
+    let mut vec = heapless::Vec::new();
+    vec.resize_default(N).unwrap();
+
+    let len = convert(&mut vec)?;
+    vec.truncate(len);
+    Ok(vec)
 }
 
+/// Like `into_heapless`, but for converters that write ASCII characters.
+pub(crate) fn into_heapless_string<F, const N: usize>(
+    convert: F,
+) -> Result<heapless::String<N>, ConversionError>
+where
+    F: FnOnce(&mut [u8]) -> Result<usize, ConversionError>,
+{
+    // Since all chars are ASCII 'unwrap' will never fail
+    Ok(heapless::String::from_utf8(into_heapless(convert)?).unwrap())
+}
+
+#[cfg(feature = "alloc")]
 pub(crate) fn pad_right(tryte_str: &str, length: usize) -> String {
     if length <= tryte_str.len() {
         return String::from(tryte_str);
     };
 
-    let mut chars = vec![TRYTE_CODE_TO_ASCII_CODE[0]; length];
+    let mut chars = vec![TRYTE_CODE_TO_ASCII_CODE[0].as_byte(); length];
 
     let trytes = tryte_str.as_bytes();
-    chars[0..trytes.len()].copy_from_slice(&trytes[..]);
+    chars[0..trytes.len()].copy_from_slice(trytes);
 
     String::from_utf8(chars).unwrap()
 }
 
+#[cfg(feature = "alloc")]
 pub(crate) fn unpad_right(tryte_string: &str) -> String {
     match tryte_string.rfind(|c| c != '9') {
         Some(index) => String::from(&tryte_string[0..=index]),
@@ -60,33 +181,54 @@ mod tests {
     #[test]
     fn test_is_tryte_str() {
         let test_trytes = "ABCDEFGHIJKLMNOPQRSTUVWXYZ99999";
-        assert_eq!(true, is_tryte_str(test_trytes));
+        assert!(is_tryte_str(test_trytes));
 
         let test_trytes = "ABCDEfGHIJKLMNOPQRSTUVWXYZ99999";
-        assert_eq!(false, is_tryte_str(test_trytes));
+        assert!(!is_tryte_str(test_trytes));
 
         let test_trytes = "ABCDEFGHIJKLMNOPQRSTUVWXYZ99998";
-        assert_eq!(false, is_tryte_str(test_trytes));
+        assert!(!is_tryte_str(test_trytes));
     }
 
     #[test]
-    fn test_is_trytes() {
-        assert_eq!(true, is_trytes(&[57, 65, 77, 90]));
-        assert_eq!(false, is_trytes(&[56, 65, 77, 90]));
+    fn test_tryte_str() {
+        //
+        assert!(is_tryte_str("ABCD9999"));
+        assert!(!is_tryte_str("ABCD8999"));
+        assert!(!is_tryte_str("aBCD9999"));
     }
 
     #[test]
-    fn test_tryte_str() {
-        //
-        assert_eq!(true, is_tryte_str("ABCD9999"));
-        assert_eq!(false, is_tryte_str("ABCD8999"));
-        assert_eq!(false, is_tryte_str("aBCD9999"));
+    fn test_check_trytes() {
+        assert_eq!(Ok(()), check_trytes(b"AM9Z"));
+        assert_eq!(Err(ConversionError::new(ErrorKind::InvalidTryte, 2)), check_trytes(b"AMa9"));
+        assert_eq!(Err(ConversionError::new(ErrorKind::InvalidTryte, 0)), check_tryte_str("8ABC"));
+    }
+
+    #[test]
+    fn test_check_trits() {
+        assert_eq!(Ok(()), check_trits(&[0, -1, 1, -1]));
+        assert_eq!(
+            Err(ConversionError::new(ErrorKind::InvalidTrit, 3)),
+            check_trits(&[0, -1, 1, 2])
+        );
+    }
+
+    #[test]
+    fn test_check_bytes() {
+        assert_eq!(Ok(()), check_bytes(&[0, 0, 26 * 8 + 2, 26 * 8 + 3]));
+        assert_eq!(Err(ConversionError::new(ErrorKind::OutOfRange, 0)), check_bytes(&[27 * 8, 0]));
+        assert_eq!(
+            Err(ConversionError::new(ErrorKind::OutOfRange, 3)),
+            check_bytes(&[0, 0, 0, 27 * 8])
+        );
+        assert_eq!(Err(ConversionError::new(ErrorKind::OutOfRange, 2)), check_bytes(&[0, 0, 3, 3]));
     }
 
     #[test]
-    fn test_is_trits() {
-        assert_eq!(true, is_trits(&[0, -1, 1, -1]));
-        assert_eq!(false, is_trits(&[2, -1, 1, -1]));
+    fn test_check_ascii() {
+        assert_eq!(Ok(()), check_ascii("Hello"));
+        assert_eq!(Err(ConversionError::new(ErrorKind::NonAscii, 1)), check_ascii("Hé"));
     }
 
     #[test]
@@ -0,0 +1,310 @@
+//! Converter functions between 243 trits and 384 bit integers, as used by the Kerl hash function.
+//!
+//! The first 242 trits are interpreted as a balanced ternary number, least significant trit
+//! first, which is converted to the same number as a 384 bit two's complement integer in
+//! big-endian order. Since 243 trits exceed the range of 384 bits, the last trit is ignored and
+//! always zero when converting back. Integers outside the range of 242 trits, `±(3^242-1)/2`, are
+//! reduced modulo `3^242`.
+
+use core::cmp::Ordering;
+
+use crate::types::Trit;
+
+/// The number of 32 bit words of a 384 bit integer.
+const NUM_WORDS: usize = 12;
+
+/// The number of trits that are converted, the last of the 243 trits is always zero.
+const NUM_CONVERTED_TRITS: usize = 242;
+
+/// 3^242, least significant word first.
+const THREE_POW_242: [u32; NUM_WORDS] = three_pow_242();
+
+/// (3^242-1)/2, the largest absolute value of 242 trits, least significant word first.
+const HALF_THREE_POW_242: [u32; NUM_WORDS] = half_three_pow_242();
+
+/// Converts 243 trits to 48 big-endian bytes.
+pub fn trits_243_to_bytes_48(trits: &[Trit; 243]) -> [u8; 48] {
+    let mut bytes = [0_u8; 48];
+
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(trits_243_to_words_12(trits).iter()) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+
+    bytes
+}
+
+/// Converts 48 big-endian bytes to 243 trits.
+pub fn bytes_48_to_trits_243(bytes: &[u8; 48]) -> [Trit; 243] {
+    let mut words = [0_u32; NUM_WORDS];
+
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+
+    words_12_to_trits_243(&words)
+}
+
+/// Converts 243 trits to 12 words, most significant word first.
+pub fn trits_243_to_words_12(trits: &[Trit; 243]) -> [u32; 12] {
+    // Two's complement arithmetic modulo 2^384 yields the right result for negative numbers too.
+    let mut value = [0_u32; NUM_WORDS];
+
+    for &trit in trits[..NUM_CONVERTED_TRITS].iter().rev() {
+        mul_add(&mut value, trit);
+    }
+
+    value.reverse();
+    value
+}
+
+/// Converts 12 words, most significant word first, to 243 trits.
+pub fn words_12_to_trits_243(words: &[u32; 12]) -> [Trit; 243] {
+    let mut value = *words;
+    value.reverse();
+
+    let mut negative = value[NUM_WORDS - 1] >> 31 == 1;
+    if negative {
+        negate(&mut value);
+    }
+
+    // Reduce the magnitude modulo 3^242. A single subtraction suffices since 2^383 < 3^242.
+    if cmp(&value, &HALF_THREE_POW_242) == Ordering::Greater {
+        value = sub(&THREE_POW_242, &value);
+        negative = !negative;
+    }
+
+    let (pos, neg) = if negative { (Trit::Neg, Trit::Pos) } else { (Trit::Pos, Trit::Neg) };
+    let mut trits = [Trit::Zero; 243];
+
+    for trit in trits[..NUM_CONVERTED_TRITS].iter_mut() {
+        *trit = match div3(&mut value) {
+            1 => pos,
+            2 => {
+                // 2 = 3 - 1, so the next trit has to be incremented.
+                increment(&mut value);
+                neg
+            }
+            _ => Trit::Zero,
+        };
+    }
+
+    trits
+}
+
+/// Computes `value * 3 + trit` modulo 2^384.
+fn mul_add(value: &mut [u32; NUM_WORDS], trit: Trit) {
+    let mut carry = 0_u64;
+
+    for word in value.iter_mut() {
+        let product = u64::from(*word) * 3 + carry;
+        *word = product as u32;
+        carry = product >> 32;
+    }
+
+    match trit {
+        Trit::Pos => increment(value),
+        Trit::Neg => decrement(value),
+        Trit::Zero => (),
+    }
+}
+
+/// Divides the value by 3 and returns the remainder.
+fn div3(value: &mut [u32; NUM_WORDS]) -> u32 {
+    let mut remainder = 0_u64;
+
+    for word in value.iter_mut().rev() {
+        let dividend = (remainder << 32) | u64::from(*word);
+        *word = (dividend / 3) as u32;
+        remainder = dividend % 3;
+    }
+
+    remainder as u32
+}
+
+fn increment(value: &mut [u32; NUM_WORDS]) {
+    for word in value.iter_mut() {
+        let (sum, overflow) = word.overflowing_add(1);
+        *word = sum;
+        if !overflow {
+            break;
+        }
+    }
+}
+
+fn decrement(value: &mut [u32; NUM_WORDS]) {
+    for word in value.iter_mut() {
+        let (difference, underflow) = word.overflowing_sub(1);
+        *word = difference;
+        if !underflow {
+            break;
+        }
+    }
+}
+
+fn negate(value: &mut [u32; NUM_WORDS]) {
+    value.iter_mut().for_each(|word| *word = !*word);
+    increment(value);
+}
+
+fn cmp(a: &[u32; NUM_WORDS], b: &[u32; NUM_WORDS]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+/// Subtracts `b` from `a`, which must not be smaller than `b`.
+fn sub(a: &[u32; NUM_WORDS], b: &[u32; NUM_WORDS]) -> [u32; NUM_WORDS] {
+    let mut difference = [0_u32; NUM_WORDS];
+    let mut borrow = false;
+
+    for i in 0..NUM_WORDS {
+        let (d, b1) = a[i].overflowing_sub(b[i]);
+        let (d, b2) = d.overflowing_sub(u32::from(borrow));
+        difference[i] = d;
+        borrow = b1 || b2;
+    }
+
+    difference
+}
+
+const fn three_pow_242() -> [u32; NUM_WORDS] {
+    let mut value = [0_u32; NUM_WORDS];
+    value[0] = 1;
+
+    let mut n = 0;
+    while n < NUM_CONVERTED_TRITS {
+        let mut carry = 0_u64;
+        let mut i = 0;
+        while i < NUM_WORDS {
+            let product = value[i] as u64 * 3 + carry;
+            value[i] = product as u32;
+            carry = product >> 32;
+            i += 1;
+        }
+        n += 1;
+    }
+
+    value
+}
+
+const fn half_three_pow_242() -> [u32; NUM_WORDS] {
+    // 3^242 is odd, so halving it discards exactly the 1 to subtract.
+    let mut value = three_pow_242();
+
+    let mut i = 0;
+    while i < NUM_WORDS {
+        let high = if i + 1 < NUM_WORDS { value[i + 1] << 31 } else { 0 };
+        value[i] = (value[i] >> 1) | high;
+        i += 1;
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trits::from_tryte_str_array;
+    use core::convert::TryFrom;
+
+    fn from_hex(hex: &str) -> [u8; 48] {
+        let mut bytes = [0_u8; 48];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    fn to_tryte_str(trits: &[Trit; 243]) -> [u8; 81] {
+        let mut tryte_str = [0_u8; 81];
+        for (c, triplet) in tryte_str.iter_mut().zip(trits.chunks_exact(3)) {
+            *c = crate::trytes::from_trit_triplet([triplet[0], triplet[1], triplet[2]]).as_byte();
+        }
+        tryte_str
+    }
+
+    const VECTORS: [(&str, &str); 5] = [
+        (
+            "GYOMKVTSNHVJNCNFBBAH9AAMXLPLLLROQY99QN9DLSJUHDPBLCFFAIQXZA9BKMBJCYSFHFPXAHDWZFEIZ",
+            "f229bc41fdbfbef56f0380f4a7c5ca34f640492ec097af2abd7eae8b8b19f08e\
+             13acbb5244becd4ee477cb3c17b38eeb",
+        ),
+        (
+            "JYTBMLLKKINGAMNVAUYOZBRFQIXCRWG9XSLXXHVUIKOUHQWMEPZAUEBHXOTZPHRJ9AJYRODOVVODMTGYW",
+            "aab79032aec6adba83107a31279b428e7ff21e7f4832da92190621d8bf79fd77\
+             4f80bd9d4194822a91b260c1727c76bf",
+        ),
+        (
+            "MMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMD",
+            "5e69ebefa87fabdfaa06a805a9f6808b48bbae3679a4c70250979d570c24486e\
+             3ade00d91484504f9f007669a5ce8964",
+        ),
+        (
+            "Z99999999999999999999999999999999999999999999999999999999999999999999999999999999",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+             ffffffffffffffffffffffffffffffff",
+        ),
+        (
+            "999999999999999999999999999999999999999999999999999999999999999999999999999999999",
+            "0000000000000000000000000000000000000000000000000000000000000000\
+             00000000000000000000000000000000",
+        ),
+    ];
+
+    #[test]
+    fn test_trits_243_to_bytes_48() {
+        for (trytes, hex) in VECTORS.iter() {
+            assert_eq!(from_hex(hex), trits_243_to_bytes_48(&from_tryte_str_array(trytes)));
+        }
+
+        // The last trit is ignored.
+        let mut trits = [Trit::Pos; 243];
+        let max = trits_243_to_bytes_48(&trits);
+        trits[242] = Trit::Neg;
+        assert_eq!(max, trits_243_to_bytes_48(&trits));
+    }
+
+    #[test]
+    fn test_bytes_48_to_trits_243() {
+        for (trytes, hex) in VECTORS.iter() {
+            assert_eq!(
+                trytes.as_bytes(),
+                &to_tryte_str(&bytes_48_to_trits_243(&from_hex(hex)))[..]
+            );
+        }
+
+        // Out of the range of 242 trits, so reduced modulo 3^242.
+        let reduced = [
+            (
+                "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+                 ffffffffffffffffffffffffffffffff",
+                "DGKMYULNWJECTMKWJTSDPSPCODNBWDCSOEQRJAEQTTZRKCQ9NZZZTCCVJYXYXCYDVDIMLWF9MTFJDMSCX",
+            ),
+            (
+                "8000000000000000000000000000000000000000000000000000000000000000\
+                 00000000000000000000000000000000",
+                "VTPNBFOMDQVXGNPDQGHWKHKXLWMYDWXHLVJIQZVJGGAIPXJ9MAAAGXXEQBCBCXBWEWRNODU9NGUQWNHXC",
+            ),
+        ];
+        for (hex, trytes) in reduced.iter() {
+            assert_eq!(
+                trytes.as_bytes(),
+                &to_tryte_str(&bytes_48_to_trits_243(&from_hex(hex)))[..]
+            );
+        }
+    }
+
+    #[test]
+    fn test_words_roundtrip() {
+        use rand::{thread_rng, Rng};
+
+        let mut rng = thread_rng();
+
+        for _ in 0..100 {
+            let mut trits = [Trit::Zero; 243];
+            for trit in trits[..242].iter_mut() {
+                *trit = Trit::try_from(rng.gen_range(-1_i8, 2)).unwrap();
+            }
+
+            assert_eq!(trits, words_12_to_trits_243(&trits_243_to_words_12(&trits)));
+        }
+    }
+}
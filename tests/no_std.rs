@@ -0,0 +1,38 @@
+//! Makes sure the crate builds without `std` and an allocator.
+//!
+//! `checks_without_std_and_alloc` runs on the host. `builds_without_std_and_alloc` builds for an
+//! embedded target, which also makes sure nothing pulls in `std`. It is ignored by default, since
+//! it needs the target. Run it with `cargo test --test no_std -- --ignored` after
+//! `rustup target add thumbv7em-none-eabi`.
+
+use std::{path::Path, process::Command};
+
+/// The kind of target our embedded users build for.
+const EMBEDDED_TARGET: &str = "thumbv7em-none-eabi";
+
+/// Runs `cargo <command> --lib --no-default-features` with the extra arguments in a target
+/// directory of its own, and asserts that it succeeds.
+fn cargo_without_default_features(command: &str, args: &[&str], target_dir: &str) {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let output = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args([command, "--lib", "--no-default-features"])
+        .args(args)
+        .arg("--target-dir")
+        .arg(Path::new(manifest_dir).join("target").join(target_dir))
+        .output()
+        .expect("failed to run cargo");
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn checks_without_std_and_alloc() {
+    cargo_without_default_features("check", &[], "no_std_host");
+}
+
+#[test]
+#[ignore = "needs rustup target add thumbv7em-none-eabi"]
+fn builds_without_std_and_alloc() {
+    cargo_without_default_features("build", &["--target", EMBEDDED_TARGET], "no_std");
+}